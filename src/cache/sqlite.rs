@@ -1,3 +1,4 @@
+use crate::models::{UserData, UserStats};
 use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
 use rusqlite::{params, Connection};
@@ -53,7 +54,7 @@ impl CacheManager {
     })
   }
 
-  pub fn get_cached_user_data(&self, username: &str) -> Result<Option<serde_json::Value>> {
+  pub fn get_cached_user_data(&self, username: &str) -> Result<Option<UserData>> {
     let mut stmt = self
       .conn
       .prepare("SELECT user_data, cached_at, version FROM users WHERE username = ?")?;
@@ -74,15 +75,15 @@ impl CacheManager {
         if self.is_cache_expired(&cached_at)? {
           return Ok(None);
         }
-        let data = serde_json::from_str(&user_data)?;
-        Ok(Some(data))
+        // Rows that no longer match the model are treated as a miss
+        Ok(serde_json::from_str(&user_data).ok())
       }
       Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
       Err(e) => Err(e.into()),
     }
   }
 
-  pub fn get_cached_stats(&self, username: &str) -> Result<Option<serde_json::Value>> {
+  pub fn get_cached_stats(&self, username: &str) -> Result<Option<UserStats>> {
     let mut stmt = self
       .conn
       .prepare("SELECT stats_data, cached_at, version FROM users WHERE username = ?")?;
@@ -103,8 +104,8 @@ impl CacheManager {
         if self.is_cache_expired(&cached_at)? {
           return Ok(None);
         }
-        let data = serde_json::from_str(&stats_data)?;
-        Ok(Some(data))
+        // Rows that no longer match the model are treated as a miss
+        Ok(serde_json::from_str(&stats_data).ok())
       }
       Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
       Err(e) => Err(e.into()),
@@ -114,8 +115,8 @@ impl CacheManager {
   pub fn cache_user_data(
     &self,
     username: &str,
-    user_data: &serde_json::Value,
-    stats: &serde_json::Value,
+    user_data: &UserData,
+    stats: &UserStats,
   ) -> Result<()> {
     let now = Utc::now().to_rfc3339();
     let user_data_str = serde_json::to_string(user_data)?;
@@ -137,7 +138,7 @@ impl CacheManager {
   }
 
   // Get stale cache (ignore expiry, only check version)
-  pub fn get_stale_cached_user_data(&self, username: &str) -> Result<Option<serde_json::Value>> {
+  pub fn get_stale_cached_user_data(&self, username: &str) -> Result<Option<UserData>> {
     let mut stmt = self
       .conn
       .prepare("SELECT user_data, version FROM users WHERE username = ?")?;
@@ -154,15 +155,15 @@ impl CacheManager {
         if version != CACHE_VERSION {
           return Ok(None);
         }
-        let data = serde_json::from_str(&user_data)?;
-        Ok(Some(data))
+        // Rows that no longer match the model are treated as a miss
+        Ok(serde_json::from_str(&user_data).ok())
       }
       Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
       Err(e) => Err(e.into()),
     }
   }
 
-  pub fn get_stale_cached_stats(&self, username: &str) -> Result<Option<serde_json::Value>> {
+  pub fn get_stale_cached_stats(&self, username: &str) -> Result<Option<UserStats>> {
     let mut stmt = self
       .conn
       .prepare("SELECT stats_data, version FROM users WHERE username = ?")?;
@@ -179,8 +180,8 @@ impl CacheManager {
         if version != CACHE_VERSION {
           return Ok(None);
         }
-        let data = serde_json::from_str(&stats_data)?;
-        Ok(Some(data))
+        // Rows that no longer match the model are treated as a miss
+        Ok(serde_json::from_str(&stats_data).ok())
       }
       Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
       Err(e) => Err(e.into()),
@@ -1,9 +1,12 @@
+use crate::display::OutputFormat;
+use chrono::NaiveDate;
 use clap::Parser;
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(name = "gitfetch-rs")]
 #[command(
-  about = "A neofetch-style CLI tool for git.\nOriginal Python CLI is https://github.com/Matars/gitfetch\nSupports GitHub, GitLab, Gitea, and Sourcehut."
+  about = "A neofetch-style CLI tool for git.\nOriginal Python CLI is https://github.com/Matars/gitfetch\nSupports GitHub, GitLab, Gitea, Sourcehut, and Bitbucket."
 )]
 #[command(version)]
 pub struct Cli {
@@ -31,6 +34,58 @@ pub struct Cli {
   #[arg(long, help_heading = "General Options")]
   pub local: bool,
 
+  /// Merge every git repository under DIR into one local card
+  #[arg(long, value_name = "DIR", help_heading = "General Options")]
+  pub local_scan: Option<PathBuf>,
+
+  /// With --local or --local-scan, count commits by every author instead of only user.email
+  #[arg(long, help_heading = "General Options")]
+  pub all_authors: bool,
+
+  /// Use a [profiles.NAME] table from the config instead of default_profile
+  #[arg(long, value_name = "NAME", help_heading = "General Options")]
+  pub profile: Option<String>,
+
+  /// Show a repository card for owner/name instead of a user
+  #[arg(long, value_name = "OWNER/NAME", help_heading = "General Options")]
+  pub repo: Option<String>,
+
+  /// Team view of an organization (GitHub org, GitLab group, Gitea org)
+  #[arg(long, value_name = "NAME", help_heading = "General Options")]
+  pub org: Option<String>,
+
+  /// Combine every account configured under [accounts] into one card
+  #[arg(long, conflicts_with_all = ["repo", "org", "history"], help_heading = "General Options")]
+  pub all_accounts: bool,
+
+  /// Show contributions for a calendar year instead of the last 365 days
+  #[arg(long, value_name = "YYYY", conflicts_with_all = ["from", "to"], help_heading = "General Options")]
+  pub year: Option<i32>,
+
+  /// Start the contribution calendar at this date (defaults to a year before --to)
+  #[arg(long, value_name = "YYYY-MM-DD", help_heading = "General Options")]
+  pub from: Option<NaiveDate>,
+
+  /// End the contribution calendar at this date (defaults to today)
+  #[arg(long, value_name = "YYYY-MM-DD", help_heading = "General Options")]
+  pub to: Option<NaiveDate>,
+
+  /// Include private contributions when showing the token owner (GitHub)
+  #[arg(long, help_heading = "General Options")]
+  pub include_private: bool,
+
+  /// One calendar per year since the account was created, with all-time totals
+  #[arg(long, conflicts_with_all = ["year", "from", "to", "output"], help_heading = "General Options")]
+  pub history: bool,
+
+  /// Output format; json, yaml and toml print a versioned document, html a standalone report
+  #[arg(long, value_enum, default_value_t = OutputFormat::Text, help_heading = "General Options")]
+  pub format: OutputFormat,
+
+  /// Write the card to a file instead of the terminal (.svg, .png, .html), or the --format document for other extensions
+  #[arg(long, help_heading = "General Options")]
+  pub output: Option<PathBuf>,
+
   // ===== Visual Options =====
   /// Enable spaced layout
   #[arg(long, help_heading = "Visual Options")]
@@ -68,6 +123,10 @@ pub struct Cli {
   #[arg(long, help_heading = "Visual Options")]
   pub graph_timeline: bool,
 
+  /// Show languages as one stacked bar, like GitHub's language strip
+  #[arg(long, help_heading = "Visual Options")]
+  pub language_bar: bool,
+
   // ===== Visibility =====
   /// Hide month/date labels on contribution graph
   #[arg(long, help_heading = "Visibility")]
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use dialoguer::{Input, Select, theme::Theme};
+use dialoguer::{theme::Theme, Input, Select};
 use std::fmt;
 
 /// Custom theme that matches Python gitfetch's provider selection UI
@@ -43,7 +43,13 @@ impl Theme for GitfetchTheme {
 }
 
 pub fn prompt_provider() -> Result<String> {
-  let providers = vec!["GitHub", "GitLab", "Gitea/Forgejo/Codeberg", "Sourcehut"];
+  let providers = vec![
+    "GitHub",
+    "GitLab",
+    "Gitea/Forgejo/Codeberg",
+    "Sourcehut",
+    "Bitbucket Cloud/Data Center",
+  ];
 
   println!("Choose your git provider:");
   println!();
@@ -61,17 +67,21 @@ pub fn prompt_provider() -> Result<String> {
     1 => "gitlab",
     2 => "gitea",
     3 => "sourcehut",
+    4 => "bitbucket",
     _ => unreachable!(),
   };
 
   Ok(provider.to_string())
 }
 
-pub fn prompt_url(provider: &str) -> Result<String> {
-  Input::new()
-    .with_prompt(format!("Enter {} instance URL", provider))
-    .interact_text()
-    .map_err(Into::into)
+pub fn prompt_url(provider: &str, default: Option<&str>) -> Result<String> {
+  let mut input = Input::new().with_prompt(format!("Enter {} instance URL", provider));
+
+  if let Some(default) = default {
+    input = input.default(default.to_string());
+  }
+
+  input.interact_text().map_err(Into::into)
 }
 
 pub fn prompt_token(provider: &str) -> Result<String> {
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -13,7 +14,90 @@ pub struct Config {
   pub custom_box: Option<String>,
   #[serde(default = "default_show_date")]
   pub show_date: bool,
+  #[serde(default)]
+  pub language_weighting: LanguageWeighting,
+  /// Profile used when `--profile` isn't given
+  pub default_profile: Option<String>,
   pub colors: ColorConfig,
+  /// Per-language bar colors, overriding the linguist ones
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub language_colors: BTreeMap<String, String>,
+  /// Extra emails (or author names) that count as you in local analysis
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub author_aliases: Vec<String>,
+  /// Named accounts aggregated by `--all-accounts`
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub accounts: BTreeMap<String, AccountConfig>,
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub profiles: BTreeMap<String, ProfileConfig>,
+}
+
+impl Config {
+  /// The effective settings for `[profiles.<name>]`: keys the profile sets
+  /// override the top-level ones. A profile that picks its own provider
+  /// doesn't inherit the top-level URL, token or username, which belong to
+  /// another provider.
+  pub fn with_profile(&self, name: &str) -> Option<Config> {
+    let profile = self.profiles.get(name)?;
+    let mut config = self.clone();
+
+    if profile.provider.is_some() {
+      config.provider_url = None;
+      config.token = None;
+      config.default_username = None;
+    }
+
+    let overrides = [
+      (&mut config.provider, &profile.provider),
+      (&mut config.provider_url, &profile.provider_url),
+      (&mut config.token, &profile.token),
+      (&mut config.default_username, &profile.default_username),
+      (&mut config.custom_box, &profile.custom_box),
+    ];
+    for (value, profile_value) in overrides {
+      if profile_value.is_some() {
+        value.clone_from(profile_value);
+      }
+    }
+    if let Some(minutes) = profile.cache_expiry_minutes {
+      config.cache_expiry_minutes = minutes;
+    }
+    if let Some(show_date) = profile.show_date {
+      config.show_date = show_date;
+    }
+    if let Some(colors) = &profile.colors {
+      config.colors = colors.clone();
+    }
+    if let Some(aliases) = &profile.author_aliases {
+      config.author_aliases = aliases.clone();
+    }
+
+    Some(config)
+  }
+}
+
+/// One `[profiles.<name>]` table; unset keys fall back to the top level
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProfileConfig {
+  pub provider: Option<String>,
+  pub provider_url: Option<String>,
+  pub token: Option<String>,
+  pub default_username: Option<String>,
+  pub cache_expiry_minutes: Option<u32>,
+  pub custom_box: Option<String>,
+  pub show_date: Option<bool>,
+  pub colors: Option<ColorConfig>,
+  pub author_aliases: Option<Vec<String>>,
+}
+
+/// One `[accounts.<name>]` entry
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AccountConfig {
+  pub provider: String,
+  pub provider_url: Option<String>,
+  pub token: Option<String>,
+  /// Defaults to the token's authenticated user
+  pub username: Option<String>,
 }
 
 fn default_show_date() -> bool {
@@ -30,11 +114,28 @@ impl Default for Config {
       cache_expiry_minutes: 15,
       custom_box: None,
       show_date: true,
+      language_weighting: LanguageWeighting::default(),
       colors: ColorConfig::default(),
+      language_colors: BTreeMap::new(),
+      author_aliases: Vec::new(),
+      default_profile: None,
+      accounts: BTreeMap::new(),
+      profiles: BTreeMap::new(),
     }
   }
 }
 
+/// How repositories count towards the language breakdown
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LanguageWeighting {
+  /// Each repository's primary language gets one vote
+  Repos,
+  /// Per-repository language sizes, which costs one request per repository
+  #[default]
+  Bytes,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ColorConfig {
   pub level_0: String,
@@ -59,6 +160,9 @@ impl Default for ColorConfig {
 pub struct ConfigManager {
   config_path: PathBuf,
   pub config: Config,
+  /// The config as stored on disk, before any profile is applied
+  file_config: Config,
+  profile: Option<String>,
 }
 
 impl ConfigManager {
@@ -69,11 +173,17 @@ impl ConfigManager {
     let config_dir = project_dirs.config_dir();
     std::fs::create_dir_all(config_dir)?;
 
-    let config_path = config_dir.join("config.toml");
+    Self::load(config_dir.join("config.toml"))
+  }
 
+  /// Loads the config stored at `config_path`, or defaults when there is
+  /// none. A file that doesn't parse is an error rather than being replaced
+  /// by defaults on the next `save`.
+  pub fn load(config_path: PathBuf) -> Result<Self> {
     let config = if config_path.exists() {
       let content = std::fs::read_to_string(&config_path)?;
-      toml::from_str(&content).unwrap_or_else(|_| Config::default())
+      toml::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Invalid config file {}: {}", config_path.display(), e))?
     } else {
       // Use defaults if no config file exists (like Python version)
       Config::default()
@@ -81,16 +191,87 @@ impl ConfigManager {
 
     Ok(Self {
       config_path,
+      file_config: config.clone(),
       config,
+      profile: None,
     })
   }
 
+  /// Switches to a named profile, or `default_profile` when `name` is None.
+  ///
+  /// Unknown profiles are an error unless `create` is set, in which case
+  /// the next `save` adds them.
+  pub fn use_profile(&mut self, name: Option<&str>, create: bool) -> Result<()> {
+    let Some(name) = name
+      .map(String::from)
+      .or_else(|| self.file_config.default_profile.clone())
+    else {
+      return Ok(());
+    };
+
+    match self.file_config.with_profile(&name) {
+      Some(config) => self.config = config,
+      None if create => {
+        // Start the new profile from scratch so the wizard runs for it
+        self.config = Config {
+          provider: None,
+          provider_url: None,
+          token: None,
+          default_username: None,
+          ..self.file_config.clone()
+        };
+      }
+      None => {
+        let known: Vec<&str> = self
+          .file_config
+          .profiles
+          .keys()
+          .map(String::as_str)
+          .collect();
+        return Err(anyhow::anyhow!(
+          "Unknown profile '{}' (configured: {}); run with --change-provider to create it",
+          name,
+          if known.is_empty() {
+            "none".to_string()
+          } else {
+            known.join(", ")
+          }
+        ));
+      }
+    }
+
+    self.profile = Some(name);
+    Ok(())
+  }
+
+  pub fn get_profile(&self) -> Option<&str> {
+    self.profile.as_deref()
+  }
+
   pub fn is_initialized(&self) -> bool {
-    self.config.provider.is_some()
+    self.config.provider.is_some() || !self.config.accounts.is_empty()
   }
 
+  /// Writes the config back; with a profile active, the provider settings
+  /// go into that profile's table and the top level is left untouched
   pub fn save(&self) -> Result<()> {
-    let content = toml::to_string_pretty(&self.config)?;
+    let config = match &self.profile {
+      Some(name) => {
+        let mut file_config = self.file_config.clone();
+        let profile = file_config.profiles.entry(name.clone()).or_default();
+        profile.provider.clone_from(&self.config.provider);
+        profile.provider_url.clone_from(&self.config.provider_url);
+        profile.token.clone_from(&self.config.token);
+        profile
+          .default_username
+          .clone_from(&self.config.default_username);
+        profile.cache_expiry_minutes = Some(self.config.cache_expiry_minutes);
+        file_config
+      }
+      None => self.config.clone(),
+    };
+
+    let content = toml::to_string_pretty(&config)?;
     std::fs::write(&self.config_path, content)?;
     Ok(())
   }
@@ -1,3 +1,3 @@
 pub mod manager;
 
-pub use manager::{ColorConfig, Config, ConfigManager};
+pub use manager::{AccountConfig, ColorConfig, Config, ConfigManager, LanguageWeighting};
@@ -0,0 +1,91 @@
+/// A run of text sharing the same SGR attributes
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Span {
+  pub text: String,
+  pub fg: Option<(u8, u8, u8)>,
+  pub bg: Option<(u8, u8, u8)>,
+  pub bold: bool,
+}
+
+/// Splits a line produced by the terminal renderer into styled spans.
+///
+/// Only the sequences gitfetch itself emits are understood: reset, bold,
+/// 24-bit foreground/background and the bright 9x colors used by `colorize`.
+pub fn parse(line: &str) -> Vec<Span> {
+  let mut spans = Vec::new();
+  let mut current = Span::default();
+  let mut rest = line;
+
+  while let Some(start) = rest.find("\x1b[") {
+    current.text.push_str(&rest[..start]);
+    let after = &rest[start + 2..];
+
+    let Some(end) = after.find('m') else {
+      rest = after;
+      break;
+    };
+
+    if !current.text.is_empty() {
+      let style = Span {
+        text: String::new(),
+        ..current.clone()
+      };
+      spans.push(std::mem::replace(&mut current, style));
+    }
+
+    apply_sgr(&mut current, &after[..end]);
+    rest = &after[end + 1..];
+  }
+
+  current.text.push_str(rest);
+  if !current.text.is_empty() {
+    spans.push(current);
+  }
+
+  spans
+}
+
+fn apply_sgr(span: &mut Span, params: &str) {
+  let codes: Vec<u16> = params.split(';').map(|c| c.parse().unwrap_or(0)).collect();
+
+  let mut i = 0;
+  while i < codes.len() {
+    match codes[i] {
+      0 => {
+        span.fg = None;
+        span.bg = None;
+        span.bold = false;
+      }
+      1 => span.bold = true,
+      22 => span.bold = false,
+      38 | 48 if codes.get(i + 1) == Some(&2) && i + 4 < codes.len() => {
+        let rgb = (codes[i + 2] as u8, codes[i + 3] as u8, codes[i + 4] as u8);
+        if codes[i] == 38 {
+          span.fg = Some(rgb);
+        } else {
+          span.bg = Some(rgb);
+        }
+        i += 4;
+      }
+      39 => span.fg = None,
+      49 => span.bg = None,
+      code @ 90..=97 => span.fg = Some(bright_color(code - 90)),
+      _ => {}
+    }
+    i += 1;
+  }
+}
+
+/// Bright palette in the same Dracula family as the truecolor accents
+fn bright_color(index: u16) -> (u8, u8, u8) {
+  match index {
+    0 => (98, 114, 164),
+    1 => (255, 85, 85),
+    2 => (80, 250, 123),
+    3 => (241, 250, 140),
+    4 => (189, 147, 249),
+    5 => (255, 121, 198),
+    6 => (139, 233, 253),
+    _ => (255, 255, 255),
+  }
+}
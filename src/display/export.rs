@@ -0,0 +1,141 @@
+use crate::models::{IssueStats, LineChanges, PullRequestStats, RepoStats, UserData, UserStats};
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Bump whenever a field is renamed or removed from `ExportDocument`
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+  /// Terminal card
+  #[default]
+  Text,
+  Json,
+  Yaml,
+  Toml,
+  /// Standalone report with per-day tooltips
+  Html,
+}
+
+/// Machine-readable snapshot of everything the terminal card shows
+#[derive(Debug, Serialize)]
+pub struct ExportDocument<'a> {
+  pub schema_version: u32,
+  pub generator: String,
+  pub generated_at: String,
+  pub username: &'a str,
+  pub user: &'a UserData,
+  pub stats: ExportStats<'a>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportStats<'a> {
+  pub total_stars: u32,
+  pub total_forks: u32,
+  pub total_repos: u32,
+  pub total_contributions: u32,
+  pub current_streak: u32,
+  pub longest_streak: u32,
+  pub languages: BTreeMap<&'a str, f64>,
+  pub calendar: Vec<ExportDay<'a>>,
+  /// Local analysis only: files and lines touched per day
+  #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+  pub line_changes: &'a BTreeMap<String, LineChanges>,
+  /// Present when the calendar includes private contributions
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub private_contributions: Option<u32>,
+  pub pull_requests: &'a PullRequestStats,
+  pub issues: &'a IssueStats,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportDay<'a> {
+  pub date: &'a str,
+  pub count: u32,
+}
+
+impl<'a> ExportDocument<'a> {
+  pub fn new(username: &'a str, user: &'a UserData, stats: &'a UserStats) -> Self {
+    let calendar = stats
+      .contribution_graph
+      .iter()
+      .flat_map(|week| &week.contribution_days)
+      .map(|day| ExportDay {
+        date: &day.date,
+        count: day.contribution_count,
+      })
+      .collect();
+
+    Self {
+      schema_version: SCHEMA_VERSION,
+      generator: generator(),
+      generated_at: chrono::Utc::now().to_rfc3339(),
+      username,
+      user,
+      stats: ExportStats {
+        total_stars: stats.total_stars,
+        total_forks: stats.total_forks,
+        total_repos: stats.total_repos,
+        total_contributions: stats.total_contributions,
+        current_streak: stats.current_streak,
+        longest_streak: stats.longest_streak,
+        languages: stats
+          .languages
+          .iter()
+          .map(|(lang, pct)| (lang.as_str(), *pct))
+          .collect(),
+        calendar,
+        line_changes: &stats.line_changes,
+        private_contributions: stats.private_contributions,
+        pull_requests: &stats.pull_requests,
+        issues: &stats.issues,
+      },
+    }
+  }
+
+  pub fn render(&self, format: OutputFormat) -> Result<String> {
+    render_document(self, format)
+  }
+}
+
+/// Machine-readable snapshot of a `--repo` card
+#[derive(Debug, Serialize)]
+pub struct RepoExportDocument<'a> {
+  pub schema_version: u32,
+  pub generator: String,
+  pub generated_at: String,
+  pub repo: &'a RepoStats,
+}
+
+impl<'a> RepoExportDocument<'a> {
+  pub fn new(repo: &'a RepoStats) -> Self {
+    Self {
+      schema_version: SCHEMA_VERSION,
+      generator: generator(),
+      generated_at: chrono::Utc::now().to_rfc3339(),
+      repo,
+    }
+  }
+
+  pub fn render(&self, format: OutputFormat) -> Result<String> {
+    render_document(self, format)
+  }
+}
+
+fn generator() -> String {
+  format!("gitfetch-rs {}", env!("CARGO_PKG_VERSION"))
+}
+
+fn render_document<T: Serialize>(document: &T, format: OutputFormat) -> Result<String> {
+  match format {
+    OutputFormat::Json => Ok(serde_json::to_string_pretty(document)? + "\n"),
+    OutputFormat::Yaml => Ok(serde_yaml_ng::to_string(document)?),
+    OutputFormat::Toml => Ok(toml::to_string_pretty(document)?),
+    OutputFormat::Text | OutputFormat::Html => Err(anyhow::anyhow!(
+      "{:?} output is rendered by DisplayFormatter",
+      format
+    )),
+  }
+}
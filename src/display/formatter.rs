@@ -1,7 +1,17 @@
 use super::graph::ContributionGraph;
+use super::{colors, linguist, raster};
 use crate::config::Config;
+use crate::models::{LineChanges, RepoStats, SearchResult, UserData, UserStats};
+use crate::utils::calendar::{self, DateRange, YearCalendar};
 use anyhow::Result;
-use serde_json::Value;
+use std::collections::HashMap;
+
+/// Cells taken by one language row: label, bar and percentage
+pub(super) const LANGUAGE_ROW_WIDTH: usize = 12 + 1 + 24 + 1 + 6;
+/// Stacked bar color for languages outside the top five
+const OTHER_LANGUAGES_COLOR: &str = "#44475a";
+/// Columns the PNG layout is sized for, wide enough to include languages
+const PNG_LAYOUT_WIDTH: usize = 140;
 
 #[derive(Debug)]
 enum Layout {
@@ -15,6 +25,8 @@ pub struct VisualOptions {
   pub graph_only: bool,
   pub spaced: bool,
   pub graph_timeline: bool,
+  /// One stacked language bar instead of a bar per language
+  pub language_bar: bool,
   pub width: Option<usize>,
   pub height: Option<usize>,
   pub no_achievements: bool,
@@ -23,13 +35,15 @@ pub struct VisualOptions {
   pub no_pr: bool,
   pub no_account: bool,
   pub no_grid: bool,
+  /// Window the calendar covers, for headers; `None` is the rolling year
+  pub range: Option<DateRange>,
 }
 
 pub struct DisplayFormatter {
-  config: Config,
+  pub(super) config: Config,
   terminal_width: usize,
   terminal_height: usize,
-  visual_opts: VisualOptions,
+  pub(super) visual_opts: VisualOptions,
 }
 
 impl DisplayFormatter {
@@ -44,7 +58,21 @@ impl DisplayFormatter {
     })
   }
 
-  pub fn display(&self, username: &str, user_data: &Value, stats: &Value) -> Result<()> {
+  /// Calendar window for headers: "this year", "in 2024", ..., marked when
+  /// the counts include private contributions
+  pub(super) fn period(&self, stats: &UserStats) -> String {
+    let period = self
+      .visual_opts
+      .range
+      .map(|range| range.describe())
+      .unwrap_or_else(|| "this year".to_string());
+    match stats.private_contributions {
+      Some(_) => format!("{} (incl. private)", period),
+      None => period,
+    }
+  }
+
+  pub fn display(&self, username: &str, user_data: &UserData, stats: &UserStats) -> Result<()> {
     // Handle --graph-timeline option
     if self.visual_opts.graph_timeline {
       let timeline = crate::utils::timeline::get_git_timeline_graph(false)?;
@@ -68,7 +96,7 @@ impl DisplayFormatter {
     let layout = self.determine_layout(username, user_data, stats);
 
     match layout {
-      Layout::Minimal => self.display_minimal(username, stats)?,
+      Layout::Minimal => self.display_minimal(username, user_data, stats)?,
       Layout::Compact => self.display_compact(username, user_data, stats)?,
       Layout::Full => self.display_full(username, user_data, stats)?,
     }
@@ -77,7 +105,7 @@ impl DisplayFormatter {
     Ok(())
   }
 
-  fn determine_layout(&self, username: &str, user_data: &Value, stats: &Value) -> Layout {
+  fn determine_layout(&self, username: &str, user_data: &UserData, stats: &UserStats) -> Layout {
     // Try layouts in order: full -> compact -> minimal
     // Choose the first one that fits in terminal dimensions
     let layouts = vec![Layout::Full, Layout::Compact, Layout::Minimal];
@@ -98,8 +126,8 @@ impl DisplayFormatter {
   fn calculate_layout_dimensions(
     &self,
     username: &str,
-    user_data: &Value,
-    stats: &Value,
+    user_data: &UserData,
+    stats: &UserStats,
     layout: &Layout,
   ) -> (usize, usize) {
     match layout {
@@ -109,7 +137,7 @@ impl DisplayFormatter {
     }
   }
 
-  fn calculate_minimal_dimensions(&self, _username: &str, _stats: &Value) -> (usize, usize) {
+  fn calculate_minimal_dimensions(&self, _username: &str, _stats: &UserStats) -> (usize, usize) {
     if !self.visual_opts.no_grid {
       let width = self
         .visual_opts
@@ -126,8 +154,8 @@ impl DisplayFormatter {
   fn calculate_compact_dimensions(
     &self,
     _username: &str,
-    user_data: &Value,
-    stats: &Value,
+    user_data: &UserData,
+    stats: &UserStats,
   ) -> (usize, usize) {
     let graph_width = self
       .visual_opts
@@ -139,10 +167,15 @@ impl DisplayFormatter {
       self.visual_opts.height.unwrap_or(7) + 1
     } else {
       // Just header dimensions when no grid
-      let graph = ContributionGraph::from_json(&stats["contribution_graph"]);
+      let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
       let total_contribs = graph.calculate_total_contributions();
-      let name = user_data["name"].as_str().unwrap_or("unknown");
-      let header_text = format!("{} - {} contributions this year", name, total_contribs);
+      let name = user_data.name.as_deref().unwrap_or("unknown");
+      let header_text = format!(
+        "{} - {} contributions {}",
+        name,
+        total_contribs,
+        self.period(stats)
+      );
       return (self.display_width(&header_text), 1);
     };
 
@@ -150,15 +183,20 @@ impl DisplayFormatter {
     let mut right_lines = Vec::new();
 
     if !self.visual_opts.no_account {
-      let graph = ContributionGraph::from_json(&stats["contribution_graph"]);
+      let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
       let total_contribs = graph.calculate_total_contributions();
-      let name = user_data["name"].as_str().unwrap_or("unknown");
-      let info_text = format!("{} - {} contributions this year", name, total_contribs);
+      let name = user_data.name.as_deref().unwrap_or("unknown");
+      let info_text = format!(
+        "{} - {} contributions {}",
+        name,
+        total_contribs,
+        self.period(stats)
+      );
       right_lines.push(info_text);
     }
 
     if !self.visual_opts.no_achievements {
-      let graph = ContributionGraph::from_json(&stats["contribution_graph"]);
+      let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
       let (current_streak, max_streak) = graph.calculate_streaks();
       let total_contribs = graph.calculate_total_contributions();
 
@@ -194,8 +232,8 @@ impl DisplayFormatter {
   fn calculate_full_dimensions(
     &self,
     _username: &str,
-    user_data: &Value,
-    stats: &Value,
+    user_data: &UserData,
+    stats: &UserStats,
   ) -> (usize, usize) {
     let graph_width = self
       .visual_opts
@@ -234,7 +272,7 @@ impl DisplayFormatter {
     let mut right_lines = Vec::new();
 
     if !self.visual_opts.no_account {
-      let graph = ContributionGraph::from_json(&stats["contribution_graph"]);
+      let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
       let total_contribs = graph.calculate_total_contributions();
 
       // Use actual format_user_info to get real line count
@@ -244,7 +282,7 @@ impl DisplayFormatter {
 
     // Only show languages if terminal width >= 120
     if !self.visual_opts.no_languages && self.terminal_width >= 120 {
-      let language_lines = self.format_languages(stats);
+      let language_lines = self.format_languages(&stats.languages);
       if !language_lines.is_empty() {
         if !right_lines.is_empty() {
           right_lines.push(String::new());
@@ -254,7 +292,7 @@ impl DisplayFormatter {
     }
 
     if !self.visual_opts.no_achievements {
-      let graph = ContributionGraph::from_json(&stats["contribution_graph"]);
+      let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
       let achievement_lines = self.format_achievements(&graph);
       if !achievement_lines.is_empty() {
         if !right_lines.is_empty() {
@@ -335,16 +373,16 @@ impl DisplayFormatter {
     // Add 2: one for display_full's println!(), one for display()'s println!()
     (left_width + 2 + right_width, max_height + 2)
   }
-  fn display_minimal(&self, username: &str, stats: &Value) -> Result<()> {
+  fn display_minimal(&self, username: &str, user_data: &UserData, stats: &UserStats) -> Result<()> {
     println!();
 
     if self.visual_opts.no_grid {
-      let graph = ContributionGraph::from_json(&stats["contribution_graph"]);
+      let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
       let total_contribs = graph.calculate_total_contributions();
-      let name = stats["name"].as_str().unwrap_or(username);
+      let name = user_data.name.as_deref().unwrap_or(username);
       println!(
-        "\x1b[38;2;118;215;161m{}\x1b[0m - \x1b[38;2;255;184;108m{}\x1b[0m \x1b[38;2;118;215;161mcontributions this year\x1b[0m",
-        name, total_contribs
+        "\x1b[38;2;118;215;161m{}\x1b[0m - \x1b[38;2;255;184;108m{}\x1b[0m \x1b[38;2;118;215;161mcontributions {}\x1b[0m",
+        name, total_contribs, self.period(stats)
       );
     } else {
       // Use terminal width constraint for minimal layout
@@ -359,10 +397,10 @@ impl DisplayFormatter {
     Ok(())
   }
 
-  fn display_compact(&self, username: &str, user_data: &Value, stats: &Value) -> Result<()> {
+  fn display_compact(&self, username: &str, user_data: &UserData, stats: &UserStats) -> Result<()> {
     println!();
 
-    let graph = ContributionGraph::from_json(&stats["contribution_graph"]);
+    let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
     let graph_width = ((self.terminal_width.saturating_sub(40) * 3) / 4).max(40);
 
     // Left side: graph lines
@@ -370,10 +408,10 @@ impl DisplayFormatter {
       self.get_contribution_graph_lines_with_width(username, stats, graph_width)?
     } else {
       let total_contribs = graph.calculate_total_contributions();
-      let name = user_data["name"].as_str().unwrap_or(username);
+      let name = user_data.name.as_deref().unwrap_or(username);
       vec![format!(
-        "\x1b[38;2;118;215;161m{}\x1b[0m - \x1b[38;2;255;184;108m{}\x1b[0m \x1b[38;2;118;215;161mcontributions this year\x1b[0m",
-        name, total_contribs
+        "\x1b[38;2;118;215;161m{}\x1b[0m - \x1b[38;2;255;184;108m{}\x1b[0m \x1b[38;2;118;215;161mcontributions {}\x1b[0m",
+        name, total_contribs, self.period(stats)
       )]
     };
 
@@ -382,10 +420,10 @@ impl DisplayFormatter {
 
     if !self.visual_opts.no_account {
       let total_contribs = graph.calculate_total_contributions();
-      let name = user_data["name"].as_str().unwrap_or(username);
+      let name = user_data.name.as_deref().unwrap_or(username);
       right_lines.push(format!(
-        "\x1b[38;2;118;215;161m{}\x1b[0m - \x1b[38;2;255;184;108m{}\x1b[0m \x1b[38;2;118;215;161mcontributions this year\x1b[0m",
-        name, total_contribs
+        "\x1b[38;2;118;215;161m{}\x1b[0m - \x1b[38;2;255;184;108m{}\x1b[0m \x1b[38;2;118;215;161mcontributions {}\x1b[0m",
+        name, total_contribs, self.period(stats)
       ));
     }
 
@@ -510,17 +548,42 @@ impl DisplayFormatter {
     combined
   }
 
-  fn display_full(&self, username: &str, user_data: &Value, stats: &Value) -> Result<()> {
+  fn display_full(&self, username: &str, user_data: &UserData, stats: &UserStats) -> Result<()> {
     println!();
 
-    let graph = ContributionGraph::from_json(&stats["contribution_graph"]);
+    for line in self.full_lines(username, user_data, stats, self.terminal_width)? {
+      println!("{}", line);
+    }
+
+    Ok(())
+  }
+
+  /// Rasterizes the full layout as `display_full` would print it on a
+  /// terminal `PNG_LAYOUT_WIDTH` columns wide, whatever the real terminal is
+  pub fn render_png(
+    &self,
+    username: &str,
+    user_data: &UserData,
+    stats: &UserStats,
+  ) -> Result<Vec<u8>> {
+    raster::render_png(&self.full_lines(username, user_data, stats, PNG_LAYOUT_WIDTH)?)
+  }
+
+  fn full_lines(
+    &self,
+    username: &str,
+    user_data: &UserData,
+    stats: &UserStats,
+    terminal_width: usize,
+  ) -> Result<Vec<String>> {
+    let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
     let total_contribs = graph.calculate_total_contributions();
 
     // Calculate graph width constraint (matching Python)
     let graph_width = self
       .visual_opts
       .width
-      .unwrap_or_else(|| ((self.terminal_width.saturating_sub(10).max(50) * 3) / 4).max(50));
+      .unwrap_or_else(|| ((terminal_width.saturating_sub(10).max(50) * 3) / 4).max(50));
 
     // Left: contribution graph + PR/Issues below (only if --no-grid is not set)
     let mut graph_lines = if !self.visual_opts.no_grid {
@@ -590,8 +653,8 @@ impl DisplayFormatter {
     }
 
     // Only show languages if terminal width >= 120 (matching Python behavior)
-    if !self.visual_opts.no_languages && self.terminal_width >= 120 {
-      let language_lines = self.format_languages(stats);
+    if !self.visual_opts.no_languages && terminal_width >= 120 {
+      let language_lines = self.format_languages(&stats.languages);
       if !language_lines.is_empty() {
         if !right_lines.is_empty() {
           right_lines.push(String::new());
@@ -610,17 +673,21 @@ impl DisplayFormatter {
       }
     }
 
-    // Side-by-side output
-    let max_left_width = graph_lines
+    Ok(self.side_by_side(&graph_lines, &right_lines))
+  }
+
+  fn side_by_side(&self, left_lines: &[String], right_lines: &[String]) -> Vec<String> {
+    let max_left_width = left_lines
       .iter()
       .map(|l| self.display_width(l))
       .max()
       .unwrap_or(0);
 
-    let max_lines = graph_lines.len().max(right_lines.len());
+    let max_lines = left_lines.len().max(right_lines.len());
+    let mut lines = Vec::with_capacity(max_lines);
     for i in 0..max_lines {
-      let left = if i < graph_lines.len() {
-        &graph_lines[i]
+      let left = if i < left_lines.len() {
+        &left_lines[i]
       } else {
         ""
       };
@@ -633,19 +700,134 @@ impl DisplayFormatter {
         ""
       };
 
-      println!("{}{}  {}", left, padding, right);
+      lines.push(format!("{}{}  {}", left, padding, right));
+    }
+
+    lines
+  }
+
+  /// Prints the `--repo` card: commit calendar and open PRs/issues on the
+  /// left, repository info, contributors and languages on the right
+  pub fn display_repo(&self, repo: &RepoStats) -> Result<()> {
+    println!();
+
+    for line in self.repo_lines(repo) {
+      println!("{}", line);
     }
 
     Ok(())
   }
 
+  pub fn render_repo_png(&self, repo: &RepoStats) -> Result<Vec<u8>> {
+    raster::render_png(&self.repo_lines(repo))
+  }
+
+  pub fn repo_lines(&self, repo: &RepoStats) -> Vec<String> {
+    let graph = ContributionGraph::from_weeks(&repo.commit_activity);
+    let total_commits = graph.calculate_total_contributions();
+    let graph_width = self
+      .visual_opts
+      .width
+      .unwrap_or_else(|| ((self.terminal_width.saturating_sub(10).max(50) * 3) / 4).max(50));
+
+    let mut left_lines = Vec::new();
+    if !self.visual_opts.no_grid {
+      let max_weeks = (graph_width.saturating_sub(4) / 2).clamp(13, 52);
+      left_lines = graph.render(
+        Some(max_weeks),
+        self.visual_opts.height,
+        self.config.custom_box.as_deref().unwrap_or("■"),
+        &self.config.colors,
+        self.config.show_date,
+        self.visual_opts.spaced,
+      );
+    }
+
+    let mut sections = Vec::new();
+    if !self.visual_opts.no_pr {
+      let mut lines = vec![
+        self.colorize("PULL REQUESTS", "header"),
+        self.colorize(&"─".repeat(13), "muted"),
+      ];
+      lines.extend(self.format_search_result("Open", &repo.pull_requests, 6));
+      sections.push(lines);
+    }
+    if !self.visual_opts.no_issues {
+      let mut lines = vec![
+        self.colorize("ISSUES", "header"),
+        self.colorize(&"─".repeat(6), "muted"),
+      ];
+      lines.extend(self.format_search_result("Open", &repo.issues, 6));
+      sections.push(lines);
+    }
+    if !sections.is_empty() {
+      if !left_lines.is_empty() {
+        left_lines.push(String::new());
+      }
+      left_lines.extend(self.combine_section_grid(&sections, graph_width));
+    }
+
+    let mut right_lines = Vec::new();
+    if !self.visual_opts.no_account {
+      right_lines.push(format!(
+        "{} - {} {}",
+        self.colorize(&repo.full_name, "header"),
+        self.colorize(&total_commits.to_string(), "orange"),
+        self.colorize("commits this year", "header")
+      ));
+      let plain = format!("{} - {} commits this year", repo.full_name, total_commits);
+      right_lines.push(self.colorize(&"─".repeat(self.display_width(&plain)), "muted"));
+
+      if let Some(description) = repo.description.as_deref() {
+        let description = description.replace('\n', " ");
+        right_lines.push(format!(
+          "{} {}",
+          self.label("About"),
+          self.truncate_text(&description, 60)
+        ));
+      }
+      if let Some(url) = repo.url.as_deref() {
+        right_lines.push(format!("{} {}", self.label("URL"), url));
+      }
+      right_lines.push(format!("{} {} ⭐", self.label("Stars"), repo.stars));
+      right_lines.push(format!("{} {}", self.label("Forks"), repo.forks));
+    }
+
+    if !repo.contributors.is_empty() {
+      if !right_lines.is_empty() {
+        right_lines.push(String::new());
+      }
+      right_lines.push(self.colorize("TOP CONTRIBUTORS", "header"));
+      right_lines.push(self.colorize(&"─".repeat(16), "muted"));
+      for contributor in &repo.contributors {
+        right_lines.push(format!(
+          "{} {} commits",
+          self.label(&self.truncate_text(&contributor.login, 11)),
+          contributor.contributions
+        ));
+      }
+    }
+
+    if !self.visual_opts.no_languages {
+      let language_lines = self.format_languages(&repo.languages);
+      if !language_lines.is_empty() {
+        if !right_lines.is_empty() {
+          right_lines.push(String::new());
+        }
+        right_lines.extend(language_lines);
+      }
+    }
+
+    self.side_by_side(&left_lines, &right_lines)
+  }
+
   fn get_contribution_graph_lines_with_width(
     &self,
     _username: &str,
-    stats: &Value,
+    stats: &UserStats,
     width_constraint: usize,
   ) -> Result<Vec<String>> {
-    let graph = ContributionGraph::from_json(&stats["contribution_graph"]);
+    let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
     let custom_box = self.config.custom_box.as_deref().unwrap_or("■");
     let show_date = self.config.show_date;
     let spaced = self.visual_opts.spaced;
@@ -655,7 +837,7 @@ impl DisplayFormatter {
     let block_width = 2;
     let header_margin = 4;
     let available_for_graph = width_constraint.saturating_sub(header_margin);
-    let max_weeks = (available_for_graph / block_width).max(13).min(52);
+    let max_weeks = (available_for_graph / block_width).clamp(13, 52);
 
     // Use calculated weeks as width
     let lines = graph.render(
@@ -670,6 +852,191 @@ impl DisplayFormatter {
     Ok(lines)
   }
 
+  /// Prints commit counts per repository for `--local-scan`
+  pub fn display_repo_commits(&self, repos: &[(String, u32)]) -> Result<()> {
+    println!();
+    for line in self.repo_commit_lines(repos) {
+      println!("  {}", line);
+    }
+    println!();
+    Ok(())
+  }
+
+  /// Repositories with commits, in the given order, followed by a count of
+  /// the ones without
+  pub fn repo_commit_lines(&self, repos: &[(String, u32)]) -> Vec<String> {
+    let mut lines = Vec::new();
+    lines.push(self.colorize("REPOSITORIES", "header"));
+    lines.push(self.colorize(&"─".repeat(12), "muted"));
+    lines.push(self.colorize(&format!("{:>7}  {}", "Commits", "Repository"), "muted"));
+
+    let mut idle = 0;
+    for (name, commits) in repos {
+      if *commits == 0 {
+        idle += 1;
+        continue;
+      }
+      lines.push(format!(
+        "{:>7}  {}",
+        commits,
+        self.colorize(&self.truncate_text(name, 60), "bold")
+      ));
+    }
+
+    if idle > 0 {
+      lines.push(self.colorize(
+        &format!(
+          "{} more {} without commits",
+          idle,
+          if idle == 1 {
+            "repository"
+          } else {
+            "repositories"
+          }
+        ),
+        "muted",
+      ));
+    }
+
+    lines
+  }
+
+  /// Prints one calendar per year, oldest first, then all-time figures
+  pub fn display_history(&self, name: &str, years: &[YearCalendar]) -> Result<()> {
+    println!();
+    for line in self.history_lines(name, years) {
+      println!("{}", line);
+    }
+    println!();
+    Ok(())
+  }
+
+  pub fn history_lines(&self, name: &str, years: &[YearCalendar]) -> Vec<String> {
+    let custom_box = self.config.custom_box.as_deref().unwrap_or("■");
+    // Same margins as the regular graph, but a whole year may take 53 weeks
+    let max_weeks = (self.terminal_width.saturating_sub(8) / 2).max(13);
+
+    let mut lines = Vec::new();
+    for calendar in years {
+      lines.push(format!(
+        "    {} - {} contributions, longest streak {}",
+        self.colorize(&calendar.year.to_string(), "header"),
+        self.colorize(&calendar.total().to_string(), "orange"),
+        self.colorize(&format!("{}d", calendar.longest_streak()), "orange")
+      ));
+      let graph = ContributionGraph::from_weeks(&calendar.weeks);
+      lines.extend(graph.render(
+        Some(calendar.weeks.len().min(max_weeks)),
+        self.visual_opts.height,
+        custom_box,
+        &self.config.colors,
+        self.config.show_date,
+        self.visual_opts.spaced,
+      ));
+      lines.push(String::new());
+    }
+
+    let summary = calendar::history_summary(years);
+    let header = format!("{} - all time", name);
+    lines.push(format!("    {}", self.colorize(&header, "header")));
+    lines.push(format!(
+      "    {}",
+      self.colorize(&"─".repeat(self.display_width(&header)), "muted")
+    ));
+    lines.push(format!("    {} {}", self.label("Total"), summary.total));
+    if let Some((year, total)) = summary.best_year {
+      lines.push(format!(
+        "    {} {} ({})",
+        self.label("Best year"),
+        year,
+        total
+      ));
+    }
+    if let Some((month, total)) = summary.best_month {
+      lines.push(format!(
+        "    {} {} ({})",
+        self.label("Best month"),
+        month.format("%B %Y"),
+        total
+      ));
+    }
+
+    lines
+  }
+
+  /// Prints a ranking of organization members by contributions
+  pub fn display_leaderboard(&self, members: &[(&str, &UserStats)]) -> Result<()> {
+    println!();
+    for line in self.leaderboard_lines(members) {
+      println!("  {}", line);
+    }
+    println!();
+    Ok(())
+  }
+
+  pub fn leaderboard_lines(&self, members: &[(&str, &UserStats)]) -> Vec<String> {
+    let mut rows: Vec<(&str, u32, u32, u32)> = members
+      .iter()
+      .map(|(login, stats)| {
+        let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
+        let (current_streak, max_streak) = graph.calculate_streaks();
+        (
+          *login,
+          graph.calculate_total_contributions(),
+          current_streak,
+          max_streak,
+        )
+      })
+      .collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)).then_with(|| a.0.cmp(b.0)));
+
+    let name_width = rows
+      .iter()
+      .map(|row| self.display_width(row.0))
+      .max()
+      .unwrap_or(0)
+      .clamp(6, 20);
+    let pad = |text: &str| {
+      let text = self.truncate_text(text, name_width);
+      let fill = name_width.saturating_sub(self.display_width(&text));
+      format!("{}{}", text, " ".repeat(fill))
+    };
+
+    let mut lines = Vec::new();
+    lines.push(self.colorize("LEADERBOARD", "header"));
+    lines.push(self.colorize(&"─".repeat(11), "muted"));
+    lines.push(self.colorize(
+      &format!(
+        "{:>3}  {}  {:>8}  {:>7}  {:>7}",
+        "#",
+        pad("Member"),
+        "Contribs",
+        "Streak",
+        "Best"
+      ),
+      "muted",
+    ));
+
+    for (rank, (login, total, current_streak, max_streak)) in rows.iter().enumerate() {
+      let rank_text = format!("{:>3}", rank + 1);
+      let rank_text = match rank {
+        0 => self.colorize(&rank_text, "orange"),
+        1 | 2 => self.colorize(&rank_text, "yellow"),
+        _ => rank_text,
+      };
+      lines.push(format!(
+        "{}  {}  {:>8}  {:>7}  {:>7}",
+        rank_text,
+        self.colorize(&pad(login), "bold"),
+        total,
+        format!("{}d", current_streak),
+        format!("{}d", max_streak)
+      ));
+    }
+
+    lines
+  }
+
   pub fn display_simulation_from_grid(&self, grid: Vec<Vec<u8>>) -> Result<()> {
     let graph = ContributionGraph::from_grid(grid);
     let custom_box = self.config.custom_box.as_deref().unwrap_or("■");
@@ -692,26 +1059,31 @@ impl DisplayFormatter {
     Ok(())
   }
 
-  fn format_user_info(
+  pub(super) fn format_user_info(
     &self,
     _username: &str,
-    user_data: &Value,
-    stats: &Value,
+    user_data: &UserData,
+    stats: &UserStats,
     total_contribs: u32,
   ) -> Vec<String> {
     let mut lines = Vec::new();
 
-    let name = user_data["name"].as_str().unwrap_or("Unknown");
+    let name = user_data.name.as_deref().unwrap_or("Unknown");
     let header = format!(
-      "\x1b[38;2;118;215;161m{}\x1b[0m - \x1b[38;2;255;184;108m{}\x1b[0m \x1b[38;2;118;215;161mcontributions this year\x1b[0m",
-      name, total_contribs
+      "\x1b[38;2;118;215;161m{}\x1b[0m - \x1b[38;2;255;184;108m{}\x1b[0m \x1b[38;2;118;215;161mcontributions {}\x1b[0m",
+      name, total_contribs, self.period(stats)
     );
     lines.push(header);
 
-    let plain = format!("{} - {} contributions this year", name, total_contribs);
+    let plain = format!(
+      "{} - {} contributions {}",
+      name,
+      total_contribs,
+      self.period(stats)
+    );
     lines.push(self.colorize(&"─".repeat(plain.len()), "muted"));
 
-    if let Some(bio) = user_data["bio"].as_str() {
+    if let Some(bio) = user_data.bio.as_deref() {
       if !bio.is_empty() {
         let trimmed = bio.replace('\n', " ");
         let truncated = if trimmed.len() > 80 {
@@ -723,60 +1095,149 @@ impl DisplayFormatter {
       }
     }
 
-    if let Some(company) = user_data["company"].as_str() {
+    if let Some(company) = user_data.company.as_deref() {
       if !company.is_empty() {
         lines.push(format!("{} {}", self.label("Company"), company));
       }
     }
 
-    if let Some(blog) = user_data["blog"].as_str() {
+    if let Some(blog) = user_data.blog.as_deref() {
       if !blog.is_empty() {
         lines.push(format!("{} {}", self.label("Website"), blog));
       }
     }
 
     // Add stars amount
-    if let Some(total_stars) = stats["total_stars"].as_i64() {
-      lines.push(format!("{} {} ⭐", self.label("Stars"), total_stars));
+    lines.push(format!("{} {} ⭐", self.label("Stars"), stats.total_stars));
+
+    if let Some(private) = stats.private_contributions.filter(|&count| count > 0) {
+      lines.push(format!(
+        "{} {} restricted contributions",
+        self.label("Private"),
+        private
+      ));
+    }
+
+    // Diff totals only exist for local repositories
+    if !stats.line_changes.is_empty() {
+      let mut totals = LineChanges::default();
+      for changes in stats.line_changes.values() {
+        totals.add(changes);
+      }
+      lines.push(format!(
+        "{} {} changed",
+        self.label("Files"),
+        totals.files_changed
+      ));
+      lines.push(format!(
+        "{} {} / {}",
+        self.label("Lines"),
+        self.colorize(&format!("+{}", totals.additions), "green"),
+        self.colorize(&format!("-{}", totals.deletions), "red")
+      ));
     }
 
     lines
   }
 
-  fn format_languages(&self, stats: &Value) -> Vec<String> {
+  fn format_languages(&self, languages: &HashMap<String, f64>) -> Vec<String> {
     let mut lines = Vec::new();
 
-    let languages = match stats["languages"].as_object() {
-      Some(langs) if !langs.is_empty() => langs,
-      _ => return lines,
-    };
+    if languages.is_empty() {
+      return lines;
+    }
 
     lines.push(self.colorize("TOP LANGUAGES", "header"));
     lines.push(self.colorize(&"─".repeat(13), "muted"));
 
-    let mut lang_vec: Vec<_> = languages.iter().collect();
-    lang_vec.sort_by(|a, b| {
-      let a_val = a.1.as_f64().unwrap_or(0.0);
-      let b_val = b.1.as_f64().unwrap_or(0.0);
-      b_val.partial_cmp(&a_val).unwrap()
-    });
-
-    for (lang, percentage) in lang_vec.iter().take(5) {
-      let pct = percentage.as_f64().unwrap_or(0.0);
-      let lang_name = if lang.to_lowercase() == "jupyter notebook" {
-        "Jupyter"
-      } else {
-        lang
-      };
+    if self.visual_opts.language_bar {
+      lines.extend(self.format_language_bar(languages));
+      return lines;
+    }
+
+    for (lang, pct, color) in self.top_languages(languages) {
+      let bar = self.render_progress_bar(pct, 24, &color);
+      lines.push(format!(
+        "{} {} {:5.1}%",
+        self.label(short_language_name(&lang)),
+        bar,
+        pct
+      ));
+    }
 
-      let bar = self.render_progress_bar(pct, 24);
-      lines.push(format!("{} {} {:5.1}%", self.label(lang_name), bar, pct));
+    lines
+  }
+
+  /// The stacked bar followed by a legend wrapped to the same width
+  pub(super) fn format_language_bar(&self, languages: &HashMap<String, f64>) -> Vec<String> {
+    let segments = self.language_segments(languages);
+    let percentages: Vec<f64> = segments.iter().map(|(_, pct, _)| *pct).collect();
+    let widths = linguist::segment_widths(&percentages, LANGUAGE_ROW_WIDTH);
+
+    let bar: String = segments
+      .iter()
+      .zip(widths)
+      .map(|((_, _, color), width)| self.colorize_hex(&"▰".repeat(width), color))
+      .collect();
+    let mut lines = vec![bar];
+
+    let mut legend = String::new();
+    let mut legend_width = 0;
+    for (lang, pct, color) in &segments {
+      let entry = format!("{} {:.1}%", short_language_name(lang), pct);
+      let entry_width = 2 + self.display_width(&entry);
+
+      if legend_width > 0 && legend_width + 2 + entry_width > LANGUAGE_ROW_WIDTH {
+        lines.push(std::mem::take(&mut legend));
+        legend_width = 0;
+      }
+      if legend_width > 0 {
+        legend.push_str("  ");
+        legend_width += 2;
+      }
+      legend.push_str(&format!("{} {}", self.colorize_hex("●", color), entry));
+      legend_width += entry_width;
+    }
+    if !legend.is_empty() {
+      lines.push(legend);
     }
 
     lines
   }
 
-  fn format_achievements(&self, graph: &ContributionGraph) -> Vec<String> {
+  /// The five largest languages with their colors, largest first
+  pub(super) fn top_languages(
+    &self,
+    languages: &HashMap<String, f64>,
+  ) -> Vec<(String, f64, String)> {
+    let mut lang_vec: Vec<_> = languages.iter().collect();
+    lang_vec.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
+
+    lang_vec
+      .into_iter()
+      .take(5)
+      .map(|(lang, pct)| {
+        let color = linguist::language_color(lang, &self.config.language_colors);
+        (lang.clone(), *pct, color)
+      })
+      .collect()
+  }
+
+  /// Stacked bar segments: the top languages, then the rest as "Other"
+  pub(super) fn language_segments(
+    &self,
+    languages: &HashMap<String, f64>,
+  ) -> Vec<(String, f64, String)> {
+    let mut segments = self.top_languages(languages);
+    let rest = 100.0 - segments.iter().map(|(_, pct, _)| pct).sum::<f64>();
+    if rest >= 0.05 {
+      segments.push(("Other".to_string(), rest, OTHER_LANGUAGES_COLOR.to_string()));
+    }
+
+    segments
+  }
+
+  pub(super) fn format_achievements(&self, graph: &ContributionGraph) -> Vec<String> {
     let mut lines = Vec::new();
 
     let (current_streak, max_streak) = graph.calculate_streaks();
@@ -853,9 +1314,9 @@ impl DisplayFormatter {
     lines
   }
 
-  fn render_progress_bar(&self, percentage: f64, width: usize) -> String {
+  fn render_progress_bar(&self, percentage: f64, width: usize, color: &str) -> String {
     let width = width.max(1);
-    let capped = percentage.max(0.0).min(100.0);
+    let capped = percentage.clamp(0.0, 100.0);
     let filled = ((capped / 100.0) * width as f64).round() as usize;
     let filled = filled.min(width);
     let empty = width - filled;
@@ -863,18 +1324,18 @@ impl DisplayFormatter {
     let filled_segment = "▰".repeat(filled);
     let empty_segment = "▱".repeat(empty);
 
-    let colored_filled = self.colorize(&filled_segment, "green");
+    let colored_filled = self.colorize_hex(&filled_segment, color);
 
     format!("{}{}", colored_filled, empty_segment)
   }
 
-  fn label(&self, text: &str) -> String {
+  pub(super) fn label(&self, text: &str) -> String {
     let label = format!("{}:", text);
     let padded = format!("{:<12}", label);
     self.colorize(&padded, "bold")
   }
 
-  fn colorize(&self, text: &str, color: &str) -> String {
+  pub(super) fn colorize(&self, text: &str, color: &str) -> String {
     let color_code = match color {
       "header" => "\x1b[38;2;118;215;161m",
       "orange" => "\x1b[38;2;255;184;108m",
@@ -891,7 +1352,12 @@ impl DisplayFormatter {
     format!("{}{}\x1b[0m", color_code, text)
   }
 
-  fn display_width(&self, text: &str) -> usize {
+  fn colorize_hex(&self, text: &str, hex: &str) -> String {
+    let (r, g, b) = colors::hex_to_rgb(hex);
+    format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, text)
+  }
+
+  pub(super) fn display_width(&self, text: &str) -> usize {
     // Calculate actual display width after removing ANSI codes
     let ansi_pattern = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
     let clean = ansi_pattern.replace_all(text, "");
@@ -918,13 +1384,10 @@ impl DisplayFormatter {
     format!("{}{}", truncated, ellipsis)
   }
 
-  fn format_pull_requests(&self, stats: &Value) -> Vec<String> {
+  fn format_pull_requests(&self, stats: &UserStats) -> Vec<String> {
     let mut lines = Vec::new();
 
-    let prs = match stats.get("pull_requests") {
-      Some(pr_data) if pr_data.is_object() => pr_data,
-      _ => return lines,
-    };
+    let prs = &stats.pull_requests;
 
     lines.push(self.colorize("PULL REQUESTS", "header"));
     lines.push(self.colorize(&"─".repeat(13), "muted"));
@@ -933,58 +1396,21 @@ impl DisplayFormatter {
     let labels = ["Awaiting Review", "Your Open PRs", "Mentions"];
     let label_width = labels.iter().map(|s| s.len()).max().unwrap_or(0) + 2;
 
-    for (label, key) in [
-      ("Awaiting Review", "awaiting_review"),
-      ("Your Open PRs", "open"),
-      ("Mentions", "mentions"),
+    for (label, data) in [
+      ("Awaiting Review", &prs.awaiting_review),
+      ("Your Open PRs", &prs.open),
+      ("Mentions", &prs.mentions),
     ] {
-      let data = prs.get(key);
-      let total = data
-        .and_then(|d| d.get("total_count"))
-        .and_then(|t| t.as_i64())
-        .unwrap_or(0);
-
-      let label_text = format!("{}:", label);
-      let padded_label = format!("{:<width$}", label_text, width = label_width);
-      lines.push(format!(
-        "{} {}",
-        self.colorize(&padded_label, "header"),
-        total
-      ));
-
-      // Display items (max 3)
-      let items = data
-        .and_then(|d| d.get("items"))
-        .and_then(|i| i.as_array())
-        .map(|arr| &arr[..arr.len().min(3)])
-        .unwrap_or(&[]);
-
-      if items.is_empty() {
-        lines.push(format!("  {}", self.colorize("• None", "muted")));
-      } else {
-        for item in items {
-          let title = item.get("title").and_then(|t| t.as_str()).unwrap_or("");
-          let repo = item.get("repo").and_then(|r| r.as_str()).unwrap_or("");
-
-          let mut bullet = format!("• {}", self.truncate_text(title, 24));
-          if !repo.is_empty() {
-            bullet.push_str(&format!(" ({})", self.truncate_text(repo, 16)));
-          }
-          lines.push(format!("  {}", bullet));
-        }
-      }
+      lines.extend(self.format_search_result(label, data, label_width));
     }
 
     lines
   }
 
-  fn format_issues(&self, stats: &Value) -> Vec<String> {
+  fn format_issues(&self, stats: &UserStats) -> Vec<String> {
     let mut lines = Vec::new();
 
-    let issues = match stats.get("issues") {
-      Some(issue_data) if issue_data.is_object() => issue_data,
-      _ => return lines,
-    };
+    let issues = &stats.issues;
 
     lines.push(self.colorize("ISSUES", "header"));
     lines.push(self.colorize(&"─".repeat(6), "muted"));
@@ -993,48 +1419,60 @@ impl DisplayFormatter {
     let labels = ["Assigned", "Created (open)", "Mentions"];
     let label_width = labels.iter().map(|s| s.len()).max().unwrap_or(0) + 2;
 
-    for (label, key) in [
-      ("Assigned", "assigned"),
-      ("Created (open)", "created"),
-      ("Mentions", "mentions"),
+    for (label, data) in [
+      ("Assigned", &issues.assigned),
+      ("Created (open)", &issues.created),
+      ("Mentions", &issues.mentions),
     ] {
-      let data = issues.get(key);
-      let total = data
-        .and_then(|d| d.get("total_count"))
-        .and_then(|t| t.as_i64())
-        .unwrap_or(0);
+      lines.extend(self.format_search_result(label, data, label_width));
+    }
 
-      let label_text = format!("{}:", label);
-      let padded_label = format!("{:<width$}", label_text, width = label_width);
-      lines.push(format!(
-        "{} {}",
-        self.colorize(&padded_label, "header"),
-        total
-      ));
+    lines
+  }
 
-      // Display items (max 3)
-      let items = data
-        .and_then(|d| d.get("items"))
-        .and_then(|i| i.as_array())
-        .map(|arr| &arr[..arr.len().min(3)])
-        .unwrap_or(&[]);
+  fn format_search_result(
+    &self,
+    label: &str,
+    data: &SearchResult,
+    label_width: usize,
+  ) -> Vec<String> {
+    let mut lines = Vec::new();
 
-      if items.is_empty() {
-        lines.push(format!("  {}", self.colorize("• None", "muted")));
-      } else {
-        for item in items {
-          let title = item.get("title").and_then(|t| t.as_str()).unwrap_or("");
-          let repo = item.get("repo").and_then(|r| r.as_str()).unwrap_or("");
+    let label_text = format!("{}:", label);
+    let padded_label = format!("{:<width$}", label_text, width = label_width);
+    lines.push(format!(
+      "{} {}",
+      self.colorize(&padded_label, "header"),
+      data.total_count
+    ));
 
-          let mut bullet = format!("• {}", self.truncate_text(title, 24));
-          if !repo.is_empty() {
-            bullet.push_str(&format!(" ({})", self.truncate_text(repo, 16)));
-          }
-          lines.push(format!("  {}", bullet));
+    // Display items (max 3)
+    let items = &data.items[..data.items.len().min(3)];
+
+    if items.is_empty() {
+      lines.push(format!("  {}", self.colorize("• None", "muted")));
+    } else {
+      for item in items {
+        let mut bullet = String::from("• ");
+        if let Some(origin) = &item.origin {
+          bullet.push_str(&format!("[{}] ", self.truncate_text(origin, 10)));
+        }
+        bullet.push_str(&self.truncate_text(&item.title, 24));
+        if !item.repo.is_empty() {
+          bullet.push_str(&format!(" ({})", self.truncate_text(&item.repo, 16)));
         }
+        lines.push(format!("  {}", bullet));
       }
     }
 
     lines
   }
 }
+
+pub(super) fn short_language_name(lang: &str) -> &str {
+  if lang.eq_ignore_ascii_case("jupyter notebook") {
+    "Jupyter"
+  } else {
+    lang
+  }
+}
@@ -1,7 +1,17 @@
 use super::colors::get_ansi_color;
 use crate::config::ColorConfig;
+use crate::models::{ContributionWeek, LineChanges};
 use chrono::{Datelike, NaiveDate};
-use serde_json::Value;
+use std::collections::BTreeMap;
+
+const MONTHS: [&str; 12] = [
+  "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// SVG cell size and cell-plus-gap pitch, matching GitHub's profile calendar
+const SVG_CELL_SIZE: usize = 10;
+const SVG_CELL_PITCH: usize = 13;
+const SVG_MONTH_HEIGHT: usize = 18;
 
 pub struct ContributionGraph {
   weeks: Vec<Week>,
@@ -15,34 +25,24 @@ pub struct Week {
 #[derive(Clone)]
 pub struct Day {
   pub contribution_count: u32,
-  #[allow(dead_code)]
   pub date: String,
 }
 
 impl ContributionGraph {
-  pub fn from_json(data: &Value) -> Self {
-    let weeks = data
-      .as_array()
-      .map(|arr| {
-        arr
+  pub fn from_weeks(weeks: &[ContributionWeek]) -> Self {
+    let weeks = weeks
+      .iter()
+      .map(|week| Week {
+        contribution_days: week
+          .contribution_days
           .iter()
-          .map(|week| Week {
-            contribution_days: week["contributionDays"]
-              .as_array()
-              .map(|days| {
-                days
-                  .iter()
-                  .map(|day| Day {
-                    contribution_count: day["contributionCount"].as_u64().unwrap_or(0) as u32,
-                    date: day["date"].as_str().unwrap_or("").to_string(),
-                  })
-                  .collect()
-              })
-              .unwrap_or_default(),
+          .map(|day| Day {
+            contribution_count: day.contribution_count,
+            date: day.date.clone(),
           })
-          .collect()
+          .collect(),
       })
-      .unwrap_or_default();
+      .collect();
 
     Self { weeks }
   }
@@ -121,14 +121,156 @@ impl ContributionGraph {
     lines
   }
 
-  fn get_contribution_block(&self, count: u32, colors: &ColorConfig) -> String {
-    let color = match count {
+  /// Renders the grid as SVG elements with the top-left corner at (x, y).
+  /// Returns the markup along with its width and height in pixels.
+  pub fn render_svg(
+    &self,
+    width: Option<usize>,
+    colors: &ColorConfig,
+    show_date: bool,
+    x: usize,
+    y: usize,
+  ) -> (String, usize, usize) {
+    let recent_weeks = self.get_recent_weeks(width.unwrap_or(52));
+    let mut svg = String::new();
+    let mut top = y;
+
+    if show_date {
+      for (idx, month) in self.month_starts(&recent_weeks) {
+        svg.push_str(&format!(
+          "<text x=\"{}\" y=\"{}\" class=\"muted\">{}</text>\n",
+          x + idx * SVG_CELL_PITCH,
+          top + SVG_MONTH_HEIGHT - 6,
+          month
+        ));
+      }
+      top += SVG_MONTH_HEIGHT;
+    }
+
+    for (week_idx, week) in recent_weeks.iter().enumerate() {
+      for (day_idx, day) in week.contribution_days.iter().enumerate().take(7) {
+        svg.push_str(&format!(
+          "<rect x=\"{}\" y=\"{}\" width=\"{size}\" height=\"{size}\" rx=\"2\" fill=\"{}\"><title>{} contributions on {}</title></rect>\n",
+          x + week_idx * SVG_CELL_PITCH,
+          top + day_idx * SVG_CELL_PITCH,
+          Self::level_color(day.contribution_count, colors),
+          day.contribution_count,
+          day.date,
+          size = SVG_CELL_SIZE,
+        ));
+      }
+    }
+
+    let width = recent_weeks.len() * SVG_CELL_PITCH;
+    let height = top - y + 7 * SVG_CELL_PITCH;
+    (svg, width, height)
+  }
+
+  /// Renders the calendar as an HTML table, one cell per day with a hover
+  /// tooltip naming the date, its contribution count and any `changes`
+  pub fn render_html(
+    &self,
+    width: Option<usize>,
+    colors: &ColorConfig,
+    show_date: bool,
+    changes: &BTreeMap<String, LineChanges>,
+  ) -> String {
+    let recent_weeks = self.get_recent_weeks(width.unwrap_or(52));
+    let mut html = String::from("<table class=\"calendar\">\n");
+
+    if show_date {
+      let starts = self.month_starts(&recent_weeks);
+      html.push_str("<tr class=\"months\">");
+
+      if let Some(&(first, _)) = starts.first() {
+        if first > 0 {
+          html.push_str(&format!("<th colspan=\"{}\"></th>", first));
+        }
+      }
+
+      for (i, &(idx, month)) in starts.iter().enumerate() {
+        let end = starts
+          .get(i + 1)
+          .map(|&(next, _)| next)
+          .unwrap_or(recent_weeks.len());
+        html.push_str(&format!("<th colspan=\"{}\">{}</th>", end - idx, month));
+      }
+
+      html.push_str("</tr>\n");
+    }
+
+    for day_idx in 0..7 {
+      html.push_str("<tr>");
+      for week in &recent_weeks {
+        match week.contribution_days.get(day_idx) {
+          Some(day) => html.push_str(&format!(
+            "<td style=\"background: {}\" title=\"{} on {}{}\"></td>",
+            Self::level_color(day.contribution_count, colors),
+            match day.contribution_count {
+              0 => "No contributions".to_string(),
+              1 => "1 contribution".to_string(),
+              n => format!("{} contributions", n),
+            },
+            day.date,
+            changes
+              .get(&day.date)
+              .map(|c| format!(
+                " ({} {}, +{} -{})",
+                c.files_changed,
+                if c.files_changed == 1 {
+                  "file"
+                } else {
+                  "files"
+                },
+                c.additions,
+                c.deletions
+              ))
+              .unwrap_or_default()
+          )),
+          None => html.push_str("<td class=\"empty\"></td>"),
+        }
+      }
+      html.push_str("</tr>\n");
+    }
+
+    html.push_str("</table>\n");
+    html
+  }
+
+  fn level_color(count: u32, colors: &ColorConfig) -> &str {
+    match count {
       0 => &colors.level_0,
       1..=2 => &colors.level_1,
       3..=6 => &colors.level_2,
       7..=12 => &colors.level_3,
       _ => &colors.level_4,
-    };
+    }
+  }
+
+  /// Week index and name of every month that begins in `weeks`
+  fn month_starts(&self, weeks: &[Week]) -> Vec<(usize, &'static str)> {
+    let mut starts = Vec::new();
+    let mut prev_month = None;
+
+    for (idx, week) in weeks.iter().enumerate() {
+      let date = week
+        .contribution_days
+        .first()
+        .and_then(|day| NaiveDate::parse_from_str(&day.date, "%Y-%m-%d").ok());
+
+      if let Some(date) = date {
+        if prev_month != Some(date.month()) {
+          starts.push((idx, MONTHS[date.month0() as usize]));
+        }
+        prev_month = Some(date.month());
+      }
+    }
+
+    starts
+  }
+
+  fn get_contribution_block(&self, count: u32, colors: &ColorConfig) -> String {
+    let color = Self::level_color(count, colors);
 
     // Not-spaced mode: use background color for filled square (2 spaces)
     let bg_color = get_ansi_color(color).unwrap_or_default();
@@ -147,13 +289,7 @@ impl ContributionGraph {
     custom_box: &str,
     colors: &ColorConfig,
   ) -> String {
-    let color = match count {
-      0 => &colors.level_0,
-      1..=2 => &colors.level_1,
-      3..=6 => &colors.level_2,
-      7..=12 => &colors.level_3,
-      _ => &colors.level_4,
-    };
+    let color = Self::level_color(count, colors);
 
     // Spaced mode: use custom box character with foreground color + space
     // Do NOT include reset code here - it will be added at end of line
@@ -174,9 +310,7 @@ impl ContributionGraph {
       return String::new();
     }
 
-    let months = [
-      "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
-    ];
+    let months = MONTHS;
 
     let mut month_line = String::new();
 
@@ -222,7 +356,6 @@ impl ContributionGraph {
     format!("    {}", month_line)
   }
 
-  #[allow(dead_code)]
   pub fn calculate_total_contributions(&self) -> u32 {
     self
       .weeks
@@ -232,7 +365,6 @@ impl ContributionGraph {
       .sum()
   }
 
-  #[allow(dead_code)]
   pub fn calculate_streaks(&self) -> (u32, u32) {
     let mut all_contributions: Vec<u32> = self
       .weeks
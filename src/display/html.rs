@@ -0,0 +1,208 @@
+use super::formatter::DisplayFormatter;
+use super::graph::ContributionGraph;
+use super::svg::escape_xml;
+use crate::models::{SearchResult, UserData, UserStats};
+
+impl DisplayFormatter {
+  /// Renders a standalone HTML report: calendar with per-day tooltips,
+  /// account info, languages and collapsible pull request / issue lists
+  pub fn render_html(&self, username: &str, user_data: &UserData, stats: &UserStats) -> String {
+    let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
+    let total_contribs = graph.calculate_total_contributions();
+    let name = user_data.name.as_deref().unwrap_or(username);
+    let mut body = String::new();
+
+    if !self.visual_opts.no_account {
+      body.push_str(&format!(
+        "<header>\n<h1>{}</h1>\n<p class=\"total\"><strong>{}</strong> contributions {}</p>\n<dl>\n",
+        escape_xml(name),
+        total_contribs,
+        self.period(stats)
+      ));
+      let fields = [
+        ("Login", Some(user_data.login.as_str())),
+        ("Bio", user_data.bio.as_deref()),
+        ("Company", user_data.company.as_deref()),
+        ("Location", user_data.location.as_deref()),
+      ];
+      for (label, value) in fields {
+        if let Some(value) = value.filter(|v| !v.is_empty()) {
+          body.push_str(&format!(
+            "<dt>{}</dt><dd>{}</dd>\n",
+            label,
+            escape_xml(value)
+          ));
+        }
+      }
+      if let Some(blog) = user_data.blog.as_deref().filter(|b| !b.is_empty()) {
+        body.push_str(&format!(
+          "<dt>Website</dt><dd>{}</dd>\n",
+          html_link(blog, blog)
+        ));
+      }
+      body.push_str(&format!(
+        "<dt>Stars</dt><dd>{}</dd>\n</dl>\n</header>\n",
+        stats.total_stars
+      ));
+    }
+
+    if !self.visual_opts.no_grid {
+      body.push_str("<section>\n");
+      body.push_str(&graph.render_html(
+        self.visual_opts.width,
+        &self.config.colors,
+        self.config.show_date,
+        &stats.line_changes,
+      ));
+      body.push_str("</section>\n");
+    }
+
+    if !self.visual_opts.no_achievements {
+      let (current_streak, max_streak) = graph.calculate_streaks();
+      body.push_str(&format!(
+        "<section>\n<h2>Streaks</h2>\n<p>Current: <strong>{}</strong> days &middot; Best: <strong>{}</strong> days</p>\n</section>\n",
+        current_streak, max_streak
+      ));
+    }
+
+    if !self.visual_opts.no_languages && !stats.languages.is_empty() {
+      body.push_str("<section>\n<h2>Top Languages</h2>\n");
+      if self.visual_opts.language_bar {
+        let segments = self.language_segments(&stats.languages);
+        body.push_str("<div class=\"strip\">");
+        for (lang, pct, color) in &segments {
+          body.push_str(&format!(
+            "<span style=\"width: {:.1}%; background: {}\" title=\"{} {:.1}%\"></span>",
+            pct.clamp(0.0, 100.0),
+            color,
+            escape_xml(lang),
+            pct
+          ));
+        }
+        body.push_str("</div>\n<ul class=\"legend\">\n");
+        for (lang, pct, color) in &segments {
+          body.push_str(&format!(
+            "<li><span style=\"color: {}\">●</span> {} {:.1}%</li>\n",
+            color,
+            escape_xml(lang),
+            pct
+          ));
+        }
+      } else {
+        body.push_str("<ul class=\"languages\">\n");
+        for (lang, pct, color) in self.top_languages(&stats.languages) {
+          body.push_str(&format!(
+            "<li><span class=\"name\">{}</span><span class=\"bar\"><span style=\"width: {:.1}%; background: {}\"></span></span><span class=\"pct\">{:.1}%</span></li>\n",
+            escape_xml(&lang),
+            pct.clamp(0.0, 100.0),
+            color,
+            pct
+          ));
+        }
+      }
+      body.push_str("</ul>\n</section>\n");
+    }
+
+    if !self.visual_opts.no_pr {
+      let prs = &stats.pull_requests;
+      body.push_str(&html_search_section(
+        "Pull Requests",
+        &[
+          ("Awaiting Review", &prs.awaiting_review),
+          ("Your Open PRs", &prs.open),
+          ("Mentions", &prs.mentions),
+        ],
+      ));
+    }
+
+    if !self.visual_opts.no_issues {
+      let issues = &stats.issues;
+      body.push_str(&html_search_section(
+        "Issues",
+        &[
+          ("Assigned", &issues.assigned),
+          ("Created (open)", &issues.created),
+          ("Mentions", &issues.mentions),
+        ],
+      ));
+    }
+
+    format!(
+      "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>gitfetch &middot; {title}</title>\n<style>\n{style}</style>\n</head>\n<body>\n{body}<footer>Generated by gitfetch-rs {version}</footer>\n</body>\n</html>\n",
+      title = escape_xml(name),
+      style = HTML_STYLE,
+      body = body,
+      version = env!("CARGO_PKG_VERSION")
+    )
+  }
+}
+
+/// A link to `url`, or just the text when the URL isn't http(s)
+fn html_link(url: &str, text: &str) -> String {
+  let scheme = url.trim_start().to_ascii_lowercase();
+  if scheme.starts_with("http://") || scheme.starts_with("https://") {
+    format!("<a href=\"{}\">{}</a>", escape_xml(url), escape_xml(text))
+  } else {
+    escape_xml(text)
+  }
+}
+
+const HTML_STYLE: &str = "body { background: #0d1117; color: #c9d1d9; font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; margin: 2rem; }
+h1, h2 { color: #76d7a1; font-weight: normal; }
+a { color: #8be9fd; }
+dl { display: grid; grid-template-columns: max-content auto; gap: 0.25rem 1rem; }
+dt { font-weight: bold; }
+dd { margin: 0; }
+.total strong { color: #ffb86c; }
+.calendar { border-spacing: 3px; }
+.calendar td { width: 10px; height: 10px; border-radius: 2px; padding: 0; }
+.calendar th { font-weight: normal; color: #8b949e; text-align: left; white-space: nowrap; max-width: 0; overflow: visible; }
+.languages { list-style: none; padding: 0; }
+.languages li { display: flex; align-items: center; gap: 1rem; margin: 0.25rem 0; }
+.languages .name { width: 10rem; }
+.languages .bar { width: 12rem; height: 8px; background: #44475a; border-radius: 4px; overflow: hidden; }
+.languages .bar span { display: block; height: 100%; }
+.strip { display: flex; width: 34rem; height: 8px; border-radius: 4px; overflow: hidden; }
+.legend { list-style: none; padding: 0; display: flex; flex-wrap: wrap; gap: 0.25rem 1rem; }
+details { margin: 0.25rem 0; }
+summary { cursor: pointer; }
+.repo { color: #8b949e; }
+.origin { color: #bd93f9; }
+footer { margin-top: 2rem; color: #8b949e; font-size: 0.8rem; }
+";
+
+/// A titled group of collapsible pull request or issue lists
+fn html_search_section(title: &str, groups: &[(&str, &SearchResult)]) -> String {
+  let mut html = format!("<section>\n<h2>{}</h2>\n", title);
+
+  for (label, result) in groups {
+    html.push_str(&format!(
+      "<details>\n<summary>{} ({})</summary>\n<ul>\n",
+      label, result.total_count
+    ));
+
+    if result.items.is_empty() {
+      html.push_str("<li>None</li>\n");
+    }
+
+    for item in &result.items {
+      let origin = item
+        .origin
+        .as_deref()
+        .map(|origin| format!("<span class=\"origin\">[{}]</span> ", escape_xml(origin)))
+        .unwrap_or_default();
+      html.push_str(&format!(
+        "<li>{}{} <span class=\"repo\">{}#{}</span></li>\n",
+        origin,
+        html_link(&item.url, &item.title),
+        escape_xml(&item.repo),
+        item.number
+      ));
+    }
+
+    html.push_str("</ul>\n</details>\n");
+  }
+
+  html.push_str("</section>\n");
+  html
+}
@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+/// Bar color for languages linguist has no color for
+pub const DEFAULT_COLOR: &str = "#50fa7b";
+
+/// Language colors from GitHub linguist's `languages.yml`, for languages
+/// that define one
+static COLORS: &[(&str, &str)] = &[
+  ("ActionScript", "#882B0F"),
+  ("Ada", "#02f88c"),
+  ("Agda", "#315665"),
+  ("Apex", "#1797c0"),
+  ("Assembly", "#6E4C13"),
+  ("Astro", "#ff5a03"),
+  ("AutoHotkey", "#6594b9"),
+  ("Awk", "#c30e9b"),
+  ("Ballerina", "#FF5000"),
+  ("Batchfile", "#C1F12E"),
+  ("Blade", "#f7523f"),
+  ("C", "#555555"),
+  ("C#", "#178600"),
+  ("C++", "#f34b7d"),
+  ("Chapel", "#8dc63f"),
+  ("Clojure", "#db5855"),
+  ("CMake", "#DA3434"),
+  ("CoffeeScript", "#244776"),
+  ("Common Lisp", "#3fb68b"),
+  ("Crystal", "#000100"),
+  ("CSS", "#663399"),
+  ("Cuda", "#3A4E3A"),
+  ("Cython", "#fedf5b"),
+  ("D", "#ba595e"),
+  ("Dart", "#00B4AB"),
+  ("Dhall", "#dfafff"),
+  ("Dockerfile", "#384d54"),
+  ("Eiffel", "#4d6977"),
+  ("Elixir", "#6e4a7e"),
+  ("Elm", "#60B5CC"),
+  ("Emacs Lisp", "#c065db"),
+  ("Erlang", "#B83998"),
+  ("F#", "#b845fc"),
+  ("Fennel", "#fff3d7"),
+  ("Forth", "#341708"),
+  ("Fortran", "#4d41b1"),
+  ("GDScript", "#355570"),
+  ("Gleam", "#ffaff3"),
+  ("GLSL", "#5686a5"),
+  ("Go", "#00ADD8"),
+  ("Groovy", "#4298b8"),
+  ("Hack", "#878787"),
+  ("Handlebars", "#f7931e"),
+  ("Haskell", "#5e5086"),
+  ("Haxe", "#df7900"),
+  ("HCL", "#844FBA"),
+  ("HLSL", "#aace60"),
+  ("HTML", "#e34c26"),
+  ("Hy", "#7790B2"),
+  ("Idris", "#b30000"),
+  ("Janet", "#0886a5"),
+  ("Java", "#b07219"),
+  ("JavaScript", "#f1e05a"),
+  ("Jinja", "#a52a22"),
+  ("JSON", "#292929"),
+  ("Jsonnet", "#0064bd"),
+  ("Julia", "#a270ba"),
+  ("Jupyter Notebook", "#DA5B0B"),
+  ("Just", "#384d54"),
+  ("Kotlin", "#A97BFF"),
+  ("Less", "#1d365d"),
+  ("LiveScript", "#499886"),
+  ("Lua", "#000080"),
+  ("Luau", "#00A2FF"),
+  ("Makefile", "#427819"),
+  ("Markdown", "#083fa1"),
+  ("MATLAB", "#e16737"),
+  ("MDX", "#fcb32c"),
+  ("Meson", "#007800"),
+  ("Mojo", "#ff4c1f"),
+  ("MoonScript", "#ff4585"),
+  ("Move", "#4a137a"),
+  ("Nim", "#ffc200"),
+  ("Nix", "#7e7eff"),
+  ("Nushell", "#4E9906"),
+  ("Objective-C", "#438eff"),
+  ("Objective-C++", "#6866fb"),
+  ("OCaml", "#ef7a08"),
+  ("Odin", "#60AFFE"),
+  ("Pascal", "#E3F171"),
+  ("Perl", "#0298c3"),
+  ("PHP", "#4F5D95"),
+  ("PLpgSQL", "#336790"),
+  ("PowerShell", "#012456"),
+  ("Processing", "#0096D8"),
+  ("Prolog", "#74283c"),
+  ("Puppet", "#302B6D"),
+  ("PureScript", "#1D222D"),
+  ("Python", "#3572A5"),
+  ("QML", "#44a51c"),
+  ("R", "#198CE7"),
+  ("Racket", "#3c5caa"),
+  ("Raku", "#0000fb"),
+  ("Reason", "#ff5847"),
+  ("ReScript", "#ed5051"),
+  ("Roff", "#ecdebe"),
+  ("Ruby", "#701516"),
+  ("Rust", "#dea584"),
+  ("Sass", "#a53b70"),
+  ("Scala", "#c22d40"),
+  ("Scheme", "#1e4aec"),
+  ("SCSS", "#c6538c"),
+  ("Shell", "#89e051"),
+  ("Smalltalk", "#596706"),
+  ("Smarty", "#f0c040"),
+  ("Solidity", "#AA6746"),
+  ("Standard ML", "#dc566d"),
+  ("Starlark", "#76d275"),
+  ("Stata", "#1a5f91"),
+  ("Stylus", "#ff6347"),
+  ("Svelte", "#ff3e00"),
+  ("Swift", "#F05138"),
+  ("SystemVerilog", "#DAE1C2"),
+  ("Tcl", "#e4cc98"),
+  ("TeX", "#3D6117"),
+  ("TOML", "#9c4221"),
+  ("TSQL", "#e38c00"),
+  ("Twig", "#c1d026"),
+  ("TypeScript", "#3178c6"),
+  ("Typst", "#239dad"),
+  ("V", "#4f87c4"),
+  ("Vala", "#a56de2"),
+  ("Verilog", "#b2b7f8"),
+  ("VHDL", "#adb2cb"),
+  ("Vim Script", "#199f4b"),
+  ("Visual Basic .NET", "#945db7"),
+  ("Vue", "#41b883"),
+  ("WebAssembly", "#04133b"),
+  ("XSLT", "#EB8CEB"),
+  ("YAML", "#cb171e"),
+  ("Zig", "#ec915c"),
+];
+
+/// Hex color for a language: a configured override first, then linguist's.
+/// Overrides that aren't `#rrggbb` are ignored.
+///
+/// Names are matched case-insensitively, since Bitbucket reports some in
+/// lower case.
+pub fn language_color(language: &str, overrides: &BTreeMap<String, String>) -> String {
+  overrides
+    .iter()
+    .find(|(name, color)| name.eq_ignore_ascii_case(language) && is_hex_color(color))
+    .map(|(_, color)| format!("#{}", color.trim_start_matches('#')))
+    .or_else(|| {
+      COLORS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(language))
+        .map(|(_, color)| color.to_string())
+    })
+    .unwrap_or_else(|| DEFAULT_COLOR.to_string())
+}
+
+fn is_hex_color(color: &str) -> bool {
+  let hex = color.trim_start_matches('#');
+  hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Splits `width` cells between the given percentages so the segments add
+/// up exactly, giving leftover cells to the largest remainders
+pub fn segment_widths(percentages: &[f64], width: usize) -> Vec<usize> {
+  let total: f64 = percentages.iter().map(|pct| pct.max(0.0)).sum();
+  if total <= 0.0 {
+    return vec![0; percentages.len()];
+  }
+
+  let exact: Vec<f64> = percentages
+    .iter()
+    .map(|pct| pct.max(0.0) / total * width as f64)
+    .collect();
+  let mut widths: Vec<usize> = exact.iter().map(|w| w.floor() as usize).collect();
+
+  let mut order: Vec<usize> = (0..exact.len()).collect();
+  order.sort_by(|&a, &b| {
+    let rem_a = exact[a] - exact[a].floor();
+    let rem_b = exact[b] - exact[b].floor();
+    rem_b
+      .partial_cmp(&rem_a)
+      .unwrap_or(std::cmp::Ordering::Equal)
+  });
+
+  let assigned: usize = widths.iter().sum();
+  for &idx in order.iter().take(width.saturating_sub(assigned)) {
+    widths[idx] += 1;
+  }
+
+  widths
+}
@@ -1,7 +1,14 @@
+pub mod ansi;
 pub mod colors;
+pub mod export;
 pub mod formatter;
 pub mod graph;
+pub mod html;
+pub mod linguist;
+pub mod raster;
+pub mod svg;
 pub mod text_patterns;
 
+pub use export::OutputFormat;
 pub use formatter::DisplayFormatter;
 pub use formatter::VisualOptions;
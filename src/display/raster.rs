@@ -0,0 +1,153 @@
+use super::ansi;
+use ab_glyph::{Font, FontRef, GlyphId, PxScale, ScaleFont};
+use anyhow::Result;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+static REGULAR_FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSansMono.ttf");
+static BOLD_FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSansMono-Bold.ttf");
+
+const FONT_SIZE: f32 = 16.0;
+const PADDING: u32 = 16;
+const BACKGROUND: (u8, u8, u8) = (13, 17, 23);
+const FOREGROUND: (u8, u8, u8) = (201, 209, 217);
+
+/// Rasterizes terminal output lines into a PNG, laid out on a character
+/// grid the same way a terminal would (wide characters take two cells).
+///
+/// Emoji the bundled font lacks are drawn as a similar symbol it has.
+pub fn render_png(lines: &[String]) -> Result<Vec<u8>> {
+  let regular = FontRef::try_from_slice(REGULAR_FONT)?;
+  let bold = FontRef::try_from_slice(BOLD_FONT)?;
+  let scale = PxScale::from(FONT_SIZE);
+
+  let metrics = regular.as_scaled(scale);
+  let cell_width = metrics.h_advance(regular.glyph_id('M')).ceil() as u32;
+  let line_height = (metrics.height() + metrics.line_gap()).ceil() as u32;
+  let ascent = metrics.ascent();
+
+  let rows: Vec<Vec<ansi::Span>> = lines.iter().map(|line| ansi::parse(line)).collect();
+  let columns = rows
+    .iter()
+    .map(|spans| spans.iter().map(|s| s.text.width()).sum::<usize>())
+    .max()
+    .unwrap_or(0) as u32;
+
+  let mut canvas = Canvas::new(
+    columns.max(1) * cell_width + 2 * PADDING,
+    (rows.len() as u32).max(1) * line_height + 2 * PADDING,
+  );
+
+  for (row, spans) in rows.iter().enumerate() {
+    let top = PADDING + row as u32 * line_height;
+    let mut column = 0;
+
+    for span in spans {
+      let font = if span.bold { &bold } else { &regular };
+      let color = span.fg.unwrap_or(FOREGROUND);
+
+      for ch in span.text.chars() {
+        // Zero-width characters (e.g. emoji variation selectors) take no cell
+        let cells = ch.width().unwrap_or(0) as u32;
+        if cells == 0 {
+          continue;
+        }
+
+        let left = PADDING + column * cell_width;
+        if let Some(bg) = span.bg {
+          canvas.fill_rect(left, top, cells * cell_width, line_height, bg);
+        }
+
+        let glyph_id = glyph_id(font, ch);
+        if !ch.is_whitespace() && glyph_id.0 != 0 {
+          let glyph = glyph_id
+            .with_scale_and_position(scale, ab_glyph::point(left as f32, top as f32 + ascent));
+
+          if let Some(outlined) = font.outline_glyph(glyph) {
+            let bounds = outlined.px_bounds();
+            outlined.draw(|x, y, coverage| {
+              canvas.blend(
+                bounds.min.x as i64 + x as i64,
+                bounds.min.y as i64 + y as i64,
+                color,
+                coverage,
+              );
+            });
+          }
+        }
+
+        column += cells;
+      }
+    }
+  }
+
+  canvas.encode()
+}
+
+/// The font's glyph for `ch`, falling back to a look-alike for emoji
+fn glyph_id(font: &FontRef, ch: char) -> GlyphId {
+  let id = font.glyph_id(ch);
+  if id.0 != 0 || ch.is_whitespace() {
+    return id;
+  }
+
+  let substitute = match ch {
+    '⭐' => '★',
+    '🏆' => '✪',
+    '👑' => '♛',
+    '💎' => '◆',
+    '🔥' => '♨',
+    '🎖' => '✶',
+    _ => '?',
+  };
+  font.glyph_id(substitute)
+}
+
+struct Canvas {
+  width: u32,
+  height: u32,
+  pixels: Vec<u8>,
+}
+
+impl Canvas {
+  fn new(width: u32, height: u32) -> Self {
+    let (r, g, b) = BACKGROUND;
+    Self {
+      width,
+      height,
+      pixels: [r, g, b].repeat((width * height) as usize),
+    }
+  }
+
+  fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: (u8, u8, u8)) {
+    for py in y..(y + height).min(self.height) {
+      for px in x..(x + width).min(self.width) {
+        self.blend(px as i64, py as i64, color, 1.0);
+      }
+    }
+  }
+
+  fn blend(&mut self, x: i64, y: i64, color: (u8, u8, u8), alpha: f32) {
+    if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+      return;
+    }
+
+    let alpha = alpha.clamp(0.0, 1.0);
+    let idx = ((y as u32 * self.width + x as u32) * 3) as usize;
+    for (offset, channel) in [color.0, color.1, color.2].into_iter().enumerate() {
+      let base = self.pixels[idx + offset] as f32;
+      self.pixels[idx + offset] = (base + (channel as f32 - base) * alpha).round() as u8;
+    }
+  }
+
+  fn encode(&self) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    {
+      let mut encoder = png::Encoder::new(&mut buffer, self.width, self.height);
+      encoder.set_color(png::ColorType::Rgb);
+      encoder.set_depth(png::BitDepth::Eight);
+      let mut writer = encoder.write_header()?;
+      writer.write_image_data(&self.pixels)?;
+    }
+    Ok(buffer)
+  }
+}
@@ -0,0 +1,202 @@
+use super::ansi;
+use super::formatter::{short_language_name, DisplayFormatter, LANGUAGE_ROW_WIDTH};
+use super::graph::ContributionGraph;
+use super::linguist;
+use crate::models::{UserData, UserStats};
+
+impl DisplayFormatter {
+  /// Renders the full card (graph, account info, languages, achievements)
+  /// as a standalone SVG document
+  pub fn render_svg(&self, username: &str, user_data: &UserData, stats: &UserStats) -> String {
+    const PAD: usize = 20;
+    const COLUMN_GAP: usize = 30;
+    const LINE_HEIGHT: usize = 18;
+    const CHAR_WIDTH: f64 = 7.2;
+    const BAR_CHARS: usize = 24;
+
+    let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
+    let total_contribs = graph.calculate_total_contributions();
+    let mut body = String::new();
+
+    let (graph_width, graph_height) = if !self.visual_opts.no_grid {
+      let (svg, width, height) = graph.render_svg(
+        self.visual_opts.width,
+        &self.config.colors,
+        self.config.show_date,
+        PAD,
+        PAD,
+      );
+      body.push_str(&svg);
+      (width, height)
+    } else {
+      (0, 0)
+    };
+
+    // Right column: ANSI lines are reused from the terminal renderer, while
+    // language rows are drawn as real bars instead of ▰▱ glyphs
+    enum Row {
+      Text(String),
+      Language(String, f64, String),
+      LanguageBar(Vec<(f64, String)>),
+    }
+
+    let mut rows = Vec::new();
+    let push_section = |rows: &mut Vec<Row>, section: Vec<Row>| {
+      if !section.is_empty() {
+        if !rows.is_empty() {
+          rows.push(Row::Text(String::new()));
+        }
+        rows.extend(section);
+      }
+    };
+
+    if !self.visual_opts.no_account {
+      let info = self.format_user_info(username, user_data, stats, total_contribs);
+      push_section(&mut rows, info.into_iter().map(Row::Text).collect());
+    }
+
+    if !self.visual_opts.no_languages {
+      let mut section = Vec::new();
+      if !stats.languages.is_empty() {
+        section.push(Row::Text(self.colorize("TOP LANGUAGES", "header")));
+        section.push(Row::Text(self.colorize(&"─".repeat(13), "muted")));
+        if self.visual_opts.language_bar {
+          let segments = self.language_segments(&stats.languages);
+          section.push(Row::LanguageBar(
+            segments
+              .into_iter()
+              .map(|(_, pct, color)| (pct, color))
+              .collect(),
+          ));
+          // The legend is plain text, so it comes from the terminal layout
+          let legend = self.format_language_bar(&stats.languages);
+          section.extend(legend.into_iter().skip(1).map(Row::Text));
+        } else {
+          for (lang, pct, color) in self.top_languages(&stats.languages) {
+            section.push(Row::Language(lang, pct, color));
+          }
+        }
+      }
+      push_section(&mut rows, section);
+    }
+
+    if !self.visual_opts.no_achievements {
+      let achievements = self.format_achievements(&graph);
+      push_section(&mut rows, achievements.into_iter().map(Row::Text).collect());
+    }
+
+    let right_x = if graph_width > 0 {
+      PAD + graph_width + COLUMN_GAP
+    } else {
+      PAD
+    };
+    let bar_width = (BAR_CHARS as f64 * CHAR_WIDTH).round() as usize;
+    let mut right_chars = 0;
+
+    for (idx, row) in rows.iter().enumerate() {
+      let top = PAD + idx * LINE_HEIGHT;
+      let baseline = top + 13;
+
+      match row {
+        Row::Text(line) => {
+          right_chars = right_chars.max(self.display_width(line));
+          body.push_str(&svg_text_line(line, right_x, baseline));
+        }
+        Row::Language(lang, pct, color) => {
+          right_chars = right_chars.max(12 + 1 + BAR_CHARS + 7);
+          body.push_str(&svg_text_line(
+            &self.label(short_language_name(lang)),
+            right_x,
+            baseline,
+          ));
+
+          let bar_x = right_x + (13.0 * CHAR_WIDTH).round() as usize;
+          let filled = (pct.clamp(0.0, 100.0) / 100.0 * bar_width as f64).round() as usize;
+          body.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"8\" rx=\"4\" fill=\"#44475a\"/>\n",
+            bar_x,
+            top + 6,
+            bar_width
+          ));
+          body.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"8\" rx=\"4\" fill=\"{}\"/>\n",
+            bar_x,
+            top + 6,
+            filled,
+            color
+          ));
+          body.push_str(&svg_text_line(
+            &format!("{:5.1}%", pct),
+            bar_x + bar_width + (CHAR_WIDTH as usize),
+            baseline,
+          ));
+        }
+        Row::LanguageBar(segments) => {
+          right_chars = right_chars.max(LANGUAGE_ROW_WIDTH);
+          let strip_width = (LANGUAGE_ROW_WIDTH as f64 * CHAR_WIDTH).round() as usize;
+          let percentages: Vec<f64> = segments.iter().map(|(pct, _)| *pct).collect();
+
+          let mut x = right_x;
+          for ((_, color), width) in segments
+            .iter()
+            .zip(linguist::segment_widths(&percentages, strip_width))
+          {
+            body.push_str(&format!(
+              "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"8\" fill=\"{}\"/>\n",
+              x,
+              top + 6,
+              width,
+              color
+            ));
+            x += width;
+          }
+        }
+      }
+    }
+
+    let right_width = (right_chars as f64 * CHAR_WIDTH).ceil() as usize;
+    let width = right_x + right_width + PAD;
+    let height = graph_height.max(rows.len() * LINE_HEIGHT) + 2 * PAD;
+
+    format!(
+      "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+       <style>text {{ font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 12px; fill: #c9d1d9; white-space: pre; }} .muted {{ fill: #8b949e; }}</style>\n\
+       <rect width=\"100%\" height=\"100%\" rx=\"6\" fill=\"#0d1117\"/>\n\
+       {body}</svg>\n",
+      w = width,
+      h = height,
+      body = body
+    )
+  }
+}
+
+/// One line of terminal output as an SVG `<text>` element, keeping its colors
+fn svg_text_line(line: &str, x: usize, y: usize) -> String {
+  let mut text = format!("<text x=\"{}\" y=\"{}\" xml:space=\"preserve\">", x, y);
+
+  for span in ansi::parse(line) {
+    let mut attrs = String::new();
+    if let Some((r, g, b)) = span.fg {
+      attrs.push_str(&format!(" fill=\"#{:02x}{:02x}{:02x}\"", r, g, b));
+    }
+    if span.bold {
+      attrs.push_str(" font-weight=\"bold\"");
+    }
+    text.push_str(&format!(
+      "<tspan{}>{}</tspan>",
+      attrs,
+      escape_xml(&span.text)
+    ));
+  }
+
+  text.push_str("</text>\n");
+  text
+}
+
+pub(super) fn escape_xml(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
@@ -0,0 +1,247 @@
+use super::{create_fetcher, github, FetchOptions, Fetcher};
+use crate::config::AccountConfig;
+use crate::display::graph::ContributionGraph;
+use crate::models::{SearchResult, UserData, UserStats};
+use crate::utils::{calendar, languages};
+use anyhow::Result;
+use chrono::DateTime;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+
+/// How many organization members are fetched at once
+pub const MEMBER_CONCURRENCY: usize = 4;
+
+/// Data fetched for one named account
+#[derive(Debug, Clone)]
+pub struct AccountData {
+  pub name: String,
+  pub user_data: UserData,
+  pub stats: UserStats,
+}
+
+/// Fallback API location when an account has no `provider_url`
+fn default_provider_url(provider: &str) -> Option<&'static str> {
+  match provider {
+    "github" => Some(github::DEFAULT_API_URL),
+    "gitlab" => Some("https://gitlab.com"),
+    "sourcehut" => Some("https://git.sr.ht"),
+    "bitbucket" => Some("https://bitbucket.org"),
+    _ => None,
+  }
+}
+
+pub async fn fetch_account(
+  name: &str,
+  account: &AccountConfig,
+  options: &FetchOptions,
+) -> Result<AccountData> {
+  let provider_url = match account.provider_url.as_deref() {
+    Some(url) => url,
+    None => default_provider_url(&account.provider)
+      .ok_or_else(|| anyhow::anyhow!("Account {} needs a provider_url", name))?,
+  };
+  let fetcher = create_fetcher(
+    &account.provider,
+    provider_url,
+    account.token.as_deref(),
+    options,
+  )?;
+
+  let username = match &account.username {
+    Some(username) => username.clone(),
+    None => fetcher.get_authenticated_user().await?,
+  };
+
+  let user_data = fetcher.fetch_user_data(&username).await?;
+  let stats = fetcher
+    .fetch_user_stats(&username, Some(&user_data))
+    .await?;
+
+  Ok(AccountData {
+    name: name.to_string(),
+    user_data,
+    stats,
+  })
+}
+
+/// Fetches every account concurrently, returning results sorted by account
+/// name (the order of `accounts`, not of the config file)
+pub async fn fetch_accounts(
+  accounts: &BTreeMap<String, AccountConfig>,
+  options: &FetchOptions,
+) -> Vec<(String, Result<AccountData>)> {
+  let handles: Vec<_> = accounts
+    .iter()
+    .map(|(name, account)| {
+      let task_name = name.clone();
+      let account = account.clone();
+      let options = options.clone();
+      let handle = tokio::spawn(async move { fetch_account(&task_name, &account, &options).await });
+      (name.clone(), handle)
+    })
+    .collect();
+
+  join_in_order(handles).await
+}
+
+/// Fetches organization members through one shared fetcher, at most
+/// `concurrency` at a time, returning results in the given order
+pub async fn fetch_members(
+  fetcher: Arc<dyn Fetcher>,
+  members: Vec<String>,
+  concurrency: usize,
+) -> Vec<(String, Result<AccountData>)> {
+  let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+
+  let handles: Vec<_> = members
+    .into_iter()
+    .map(|login| {
+      let fetcher = Arc::clone(&fetcher);
+      let semaphore = Arc::clone(&semaphore);
+      let task_login = login.clone();
+      let handle = tokio::spawn(async move {
+        let _permit = semaphore.acquire_owned().await?;
+        let user_data = fetcher.fetch_user_data(&task_login).await?;
+        let stats = fetcher
+          .fetch_user_stats(&task_login, Some(&user_data))
+          .await?;
+        Ok(AccountData {
+          name: task_login,
+          user_data,
+          stats,
+        })
+      });
+      (login, handle)
+    })
+    .collect();
+
+  join_in_order(handles).await
+}
+
+async fn join_in_order(
+  handles: Vec<(String, JoinHandle<Result<AccountData>>)>,
+) -> Vec<(String, Result<AccountData>)> {
+  let mut results = Vec::with_capacity(handles.len());
+  for (name, handle) in handles {
+    let result = handle
+      .await
+      .unwrap_or_else(|e| Err(anyhow::anyhow!("Task failed: {}", e)));
+    results.push((name, result));
+  }
+
+  results
+}
+
+/// Combines several accounts into one profile.
+///
+/// Calendars are summed day by day and streaks recomputed from the result.
+/// Stars, forks, repos and followers are summed, languages are weighted by
+/// each account's repo count, and every pull request or issue is tagged
+/// with the account it came from. Profile text comes from the first account
+/// that has it.
+pub fn merge_accounts(accounts: &[AccountData]) -> (UserData, UserStats) {
+  let mut user_data = UserData::default();
+  let mut stats = UserStats::default();
+
+  for account in accounts {
+    let user = &account.user_data;
+    if user_data.login.is_empty() {
+      user_data.login = user.login.clone();
+      user_data.id = user.id;
+    }
+    for (merged, value) in [
+      (&mut user_data.name, &user.name),
+      (&mut user_data.bio, &user.bio),
+      (&mut user_data.company, &user.company),
+      (&mut user_data.blog, &user.blog),
+      (&mut user_data.location, &user.location),
+      (&mut user_data.email, &user.email),
+    ] {
+      if merged.is_none() {
+        merged.clone_from(value);
+      }
+    }
+    user_data.public_repos += user.public_repos;
+    user_data.followers += user.followers;
+    user_data.following += user.following;
+    user_data.created_at = earliest(user_data.created_at.take(), user.created_at.clone());
+
+    stats.total_stars += account.stats.total_stars;
+    stats.total_forks += account.stats.total_forks;
+    stats.total_repos += account.stats.total_repos;
+    if let Some(private) = account.stats.private_contributions {
+      *stats.private_contributions.get_or_insert(0) += private;
+    }
+
+    let prs = &account.stats.pull_requests;
+    merge_search(
+      &mut stats.pull_requests.awaiting_review,
+      &prs.awaiting_review,
+      &account.name,
+    );
+    merge_search(&mut stats.pull_requests.open, &prs.open, &account.name);
+    merge_search(
+      &mut stats.pull_requests.mentions,
+      &prs.mentions,
+      &account.name,
+    );
+
+    let issues = &account.stats.issues;
+    merge_search(&mut stats.issues.assigned, &issues.assigned, &account.name);
+    merge_search(&mut stats.issues.created, &issues.created, &account.name);
+    merge_search(&mut stats.issues.mentions, &issues.mentions, &account.name);
+  }
+
+  stats.language_totals = merge_language_totals(accounts);
+  stats.languages = languages::percentages(stats.language_totals.clone());
+  stats.contribution_graph = calendar::merge_calendars(
+    accounts
+      .iter()
+      .map(|account| account.stats.contribution_graph.as_slice()),
+  );
+
+  let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
+  (stats.current_streak, stats.longest_streak) = graph.calculate_streaks();
+  stats.total_contributions = graph.calculate_total_contributions();
+
+  (user_data, stats)
+}
+
+/// The earlier of two creation timestamps. Providers write them with
+/// different offsets and precision, so they're compared as instants.
+fn earliest(a: Option<String>, b: Option<String>) -> Option<String> {
+  let instant = |ts: &Option<String>| {
+    ts.as_deref()
+      .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+  };
+  match (instant(&a), instant(&b)) {
+    (Some(x), Some(y)) if y < x => b,
+    (None, Some(_)) => b,
+    _ => a.or(b),
+  }
+}
+
+fn merge_search(merged: &mut SearchResult, result: &SearchResult, origin: &str) {
+  merged.total_count += result.total_count;
+  merged.items.extend(result.items.iter().map(|item| {
+    let mut item = item.clone();
+    item.origin = Some(origin.to_string());
+    item
+  }));
+}
+
+/// Raw language amounts of every account added up, so a large account
+/// weighs more than a small one with many repos
+fn merge_language_totals(accounts: &[AccountData]) -> HashMap<String, f64> {
+  let mut totals: HashMap<String, f64> = HashMap::new();
+
+  for account in accounts {
+    for (lang, amount) in &account.stats.language_totals {
+      *totals.entry(lang.clone()).or_insert(0.0) += amount;
+    }
+  }
+
+  totals
+}
@@ -0,0 +1,583 @@
+use super::{repo, FetchOptions, Fetcher};
+use crate::models::{
+  IssueStats, PullRequestStats, RepoStats, SearchItem, SearchResult, UserData, UserStats,
+};
+use crate::utils::calendar::DateRange;
+use crate::utils::languages;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use futures_util::stream::{self, StreamExt};
+use serde_json::Value;
+
+pub const CLOUD_API_URL: &str = "https://api.bitbucket.org/2.0";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flavor {
+  /// bitbucket.org, REST API 2.0
+  Cloud,
+  /// Self-hosted Bitbucket Data Center / Server, REST API 1.0
+  DataCenter,
+}
+
+pub struct BitbucketFetcher {
+  client: reqwest::Client,
+  api_base: String,
+  flavor: Flavor,
+  token: Option<String>,
+  options: FetchOptions,
+}
+
+impl BitbucketFetcher {
+  pub fn new(base_url: &str, token: Option<&str>) -> Result<Self> {
+    let base = base_url.trim().trim_end_matches('/');
+    let host = base
+      .split_once("://")
+      .map(|(_, rest)| rest)
+      .unwrap_or(base)
+      .split('/')
+      .next()
+      .unwrap_or("")
+      .to_lowercase();
+
+    let (api_base, flavor) = if base.is_empty() || host == "bitbucket.org" {
+      (CLOUD_API_URL.to_string(), Flavor::Cloud)
+    } else if host == "api.bitbucket.org" || base.ends_with("/2.0") {
+      (base.to_string(), Flavor::Cloud)
+    } else {
+      let root = base.strip_suffix("/rest/api/1.0").unwrap_or(base);
+      (format!("{}/rest/api/1.0", root), Flavor::DataCenter)
+    };
+
+    Ok(Self {
+      client: reqwest::Client::new(),
+      api_base,
+      flavor,
+      token: token.map(String::from),
+      options: FetchOptions::default(),
+    })
+  }
+
+  pub fn with_options(mut self, options: FetchOptions) -> Self {
+    self.options = options;
+    self
+  }
+
+  async fn api_request(&self, endpoint: &str) -> Result<Value> {
+    Ok(self.send(endpoint).await?.json::<Value>().await?)
+  }
+
+  async fn send(&self, endpoint: &str) -> Result<reqwest::Response> {
+    let url = if endpoint.starts_with("http") {
+      // Absolute "next" links carry the credentials, so they must stay on
+      // the configured server
+      let base = reqwest::Url::parse(&self.api_base)?;
+      if reqwest::Url::parse(endpoint)?.origin() != base.origin() {
+        return Err(anyhow::anyhow!(
+          "Refusing to follow Bitbucket link to another host: {}",
+          endpoint
+        ));
+      }
+      endpoint.to_string()
+    } else {
+      format!("{}{}", self.api_base, endpoint)
+    };
+
+    let mut req = self.client.get(&url);
+
+    // "user:app_password" uses basic auth, anything else is a bearer token
+    if let Some(token) = &self.token {
+      req = match token.split_once(':') {
+        Some((user, password)) => req.basic_auth(user, Some(password)),
+        None => req.bearer_auth(token),
+      };
+    }
+
+    let response = req
+      .timeout(std::time::Duration::from_secs(30))
+      .send()
+      .await?;
+
+    if !response.status().is_success() {
+      return Err(anyhow::anyhow!(
+        "Bitbucket API request failed: {}",
+        response.status()
+      ));
+    }
+
+    Ok(response)
+  }
+}
+
+#[async_trait]
+impl Fetcher for BitbucketFetcher {
+  async fn get_authenticated_user(&self) -> Result<String> {
+    if self.token.is_none() {
+      return Err(anyhow::anyhow!(
+        "Token required for Bitbucket authentication"
+      ));
+    }
+
+    match self.flavor {
+      Flavor::Cloud => {
+        let data = self.api_request("/user").await?;
+        data["username"]
+          .as_str()
+          .or_else(|| data["nickname"].as_str())
+          .map(String::from)
+          .ok_or_else(|| anyhow::anyhow!("Could not get authenticated user"))
+      }
+      Flavor::DataCenter => {
+        // Data Center has no "current user" resource; every authenticated
+        // response carries the user slug in X-AUSERNAME instead
+        let response = self.send("/application-properties").await?;
+        response
+          .headers()
+          .get("x-ausername")
+          .and_then(|v| v.to_str().ok())
+          .map(String::from)
+          .ok_or_else(|| anyhow::anyhow!("Could not get authenticated user"))
+      }
+    }
+  }
+
+  async fn fetch_user_data(&self, username: &str) -> Result<UserData> {
+    let user = self.api_request(&format!("/users/{}", username)).await?;
+
+    Ok(match self.flavor {
+      Flavor::Cloud => UserData {
+        login: user["username"]
+          .as_str()
+          .or_else(|| user["nickname"].as_str())
+          .unwrap_or(username)
+          .to_string(),
+        name: user["display_name"].as_str().map(String::from),
+        blog: user["website"].as_str().map(String::from),
+        location: user["location"].as_str().map(String::from),
+        created_at: user["created_on"].as_str().map(String::from),
+        ..Default::default()
+      },
+      Flavor::DataCenter => UserData {
+        login: user["slug"].as_str().unwrap_or(username).to_string(),
+        name: user["displayName"].as_str().map(String::from),
+        email: user["emailAddress"].as_str().map(String::from),
+        ..Default::default()
+      },
+    })
+  }
+
+  async fn fetch_repo_stats(&self, repo: &str) -> Result<RepoStats> {
+    let (owner, name) = repo::split_repo(repo)?;
+    match self.flavor {
+      Flavor::Cloud => self.cloud_repo_stats(owner, name).await,
+      Flavor::DataCenter => self.data_center_repo_stats(owner, name).await,
+    }
+  }
+
+  async fn fetch_user_stats(
+    &self,
+    username: &str,
+    _user_data: Option<&UserData>,
+  ) -> Result<UserStats> {
+    // There is no contribution calendar to narrow down
+    if self.options.range.is_some() {
+      return Err(anyhow::anyhow!(
+        "Bitbucket has no contribution calendar, so --year/--from/--to only apply to --repo"
+      ));
+    }
+
+    let repos = match self.flavor {
+      Flavor::Cloud => self.fetch_cloud_repos(username).await?,
+      Flavor::DataCenter => self.fetch_data_center_repos(username).await?,
+    };
+
+    let total_forks = self.count_forks(&repos).await;
+
+    // Calculate language statistics
+    let language_totals = languages::primary_language_counts(&repos);
+
+    // Pull request dashboards only exist for the token owner
+    let is_self = self
+      .get_authenticated_user()
+      .await
+      .is_ok_and(|login| login.eq_ignore_ascii_case(username));
+
+    let pull_requests = match (self.flavor, is_self) {
+      (Flavor::DataCenter, true) => PullRequestStats {
+        awaiting_review: self
+          .dashboard_items("REVIEWER", "&participantStatus=UNAPPROVED", 5)
+          .await,
+        open: self.dashboard_items("AUTHOR", "", 5).await,
+        mentions: SearchResult::default(),
+      },
+      // Bitbucket Cloud has no reviewer-scoped pull request listing
+      (Flavor::Cloud, _) => PullRequestStats {
+        open: self.cloud_authored_items(username, 5).await,
+        ..Default::default()
+      },
+      _ => PullRequestStats::default(),
+    };
+
+    // Bitbucket delegates issue tracking to Jira
+    Ok(UserStats {
+      total_forks,
+      total_repos: repos.len() as u32,
+      languages: languages::percentages(language_totals.clone()),
+      language_totals,
+      pull_requests,
+      issues: IssueStats::default(),
+      ..Default::default()
+    })
+  }
+}
+
+impl BitbucketFetcher {
+  async fn cloud_repo_stats(&self, workspace: &str, slug: &str) -> Result<RepoStats> {
+    let range = repo::activity_range(self.options.range);
+    let base = format!("/repositories/{}/{}", workspace, slug);
+    let data = self.api_request(&base).await?;
+
+    // Watchers are the closest thing Bitbucket has to stars
+    let count = |endpoint: String| async move {
+      match self.api_request(&endpoint).await {
+        Ok(data) => data["size"].as_u64().unwrap_or(0) as u32,
+        Err(_) => 0,
+      }
+    };
+    let stars = count(format!("{}/watchers?pagelen=1", base)).await;
+    let forks = count(format!("{}/forks?pagelen=1", base)).await;
+
+    let commits = self.cloud_commits(&base, range).await.unwrap_or_else(|e| {
+      eprintln!("Warning: Failed to fetch commit activity: {}", e);
+      Vec::new()
+    });
+
+    let pull_requests = match self
+      .api_request(&format!("{}/pullrequests?state=OPEN&pagelen=5", base))
+      .await
+    {
+      Ok(prs) => {
+        let items: Vec<SearchItem> = prs["values"]
+          .as_array()
+          .into_iter()
+          .flatten()
+          .map(cloud_pr_item)
+          .collect();
+        SearchResult {
+          total_count: prs["size"].as_u64().unwrap_or(items.len() as u64) as u32,
+          items,
+        }
+      }
+      Err(_) => SearchResult::default(),
+    };
+
+    // Only the main language is known, so it gets the whole bar
+    Ok(RepoStats {
+      full_name: data["full_name"]
+        .as_str()
+        .map(String::from)
+        .unwrap_or_else(|| format!("{}/{}", workspace, slug)),
+      description: data["description"]
+        .as_str()
+        .filter(|d| !d.is_empty())
+        .map(String::from),
+      url: data["links"]["html"]["href"].as_str().map(String::from),
+      stars,
+      forks,
+      languages: languages::by_primary_language(std::slice::from_ref(&data)),
+      commit_activity: repo::commit_calendar(commits.iter().map(|(date, _)| *date), range),
+      contributors: repo::top_contributors(commits.into_iter().map(|(_, author)| author)),
+      pull_requests,
+      // Bitbucket delegates issue tracking to Jira
+      issues: SearchResult::default(),
+    })
+  }
+
+  async fn cloud_commits(&self, base: &str, range: DateRange) -> Result<Vec<(NaiveDate, String)>> {
+    // Newest first, so stop at the first page reaching past the window
+    let mut commits = Vec::new();
+    let mut next = Some(format!("{}/commits?pagelen=100", base));
+
+    for _ in 0..repo::MAX_COMMIT_PAGES {
+      let Some(endpoint) = next else { break };
+      let data = self.api_request(&endpoint).await?;
+
+      let mut reached_end = false;
+      for commit in data["values"].as_array().into_iter().flatten() {
+        let Some(date) = commit["date"].as_str().and_then(repo::commit_date) else {
+          continue;
+        };
+        if date < range.start {
+          reached_end = true;
+          break;
+        }
+        if date > range.end {
+          continue;
+        }
+        commits.push((date, cloud_commit_author(commit).unwrap_or_default()));
+      }
+
+      next = data["next"]
+        .as_str()
+        .filter(|_| !reached_end)
+        .map(String::from);
+    }
+
+    Ok(commits)
+  }
+
+  async fn data_center_repo_stats(&self, project: &str, slug: &str) -> Result<RepoStats> {
+    let range = repo::activity_range(self.options.range);
+    let base = format!("/projects/{}/repos/{}", project, slug);
+    let data = self.api_request(&base).await?;
+
+    let commits = self
+      .data_center_commits(&base, range)
+      .await
+      .unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to fetch commit activity: {}", e);
+        Vec::new()
+      });
+
+    let list = |endpoint: String| async move {
+      self.data_center_values(&endpoint).await.unwrap_or_default()
+    };
+    let forks = list(format!("{}/forks?limit=100", base)).await;
+    let prs = list(format!("{}/pull-requests?state=OPEN&limit=100", base)).await;
+
+    // Data Center has no stars, language statistics or issue tracker
+    Ok(RepoStats {
+      full_name: format!(
+        "{}/{}",
+        data["project"]["key"].as_str().unwrap_or(project),
+        data["slug"].as_str().unwrap_or(slug)
+      ),
+      description: data["description"]
+        .as_str()
+        .filter(|d| !d.is_empty())
+        .map(String::from),
+      url: data["links"]["self"][0]["href"].as_str().map(String::from),
+      forks: forks.len() as u32,
+      commit_activity: repo::commit_calendar(commits.iter().map(|(date, _)| *date), range),
+      contributors: repo::top_contributors(commits.into_iter().map(|(_, author)| author)),
+      pull_requests: SearchResult {
+        total_count: prs.len() as u32,
+        items: prs.iter().take(5).map(data_center_pr_item).collect(),
+      },
+      ..Default::default()
+    })
+  }
+
+  async fn data_center_commits(
+    &self,
+    base: &str,
+    range: DateRange,
+  ) -> Result<Vec<(NaiveDate, String)>> {
+    let mut commits = Vec::new();
+    let mut start = 0;
+
+    for _ in 0..repo::MAX_COMMIT_PAGES {
+      let endpoint = format!("{}/commits?limit=100&start={}", base, start);
+      let data = self.api_request(&endpoint).await?;
+
+      let mut reached_end = false;
+      for commit in data["values"].as_array().into_iter().flatten() {
+        let Some(date) = commit["authorTimestamp"]
+          .as_i64()
+          .and_then(chrono::DateTime::from_timestamp_millis)
+          .map(|d| d.date_naive())
+        else {
+          continue;
+        };
+        if date < range.start {
+          reached_end = true;
+          break;
+        }
+        if date > range.end {
+          continue;
+        }
+        let author = &commit["author"];
+        let login = author["slug"]
+          .as_str()
+          .or_else(|| author["name"].as_str())
+          .unwrap_or("");
+        commits.push((date, login.to_string()));
+      }
+
+      match data["nextPageStart"].as_u64() {
+        Some(next) if !reached_end && !data["isLastPage"].as_bool().unwrap_or(true) => start = next,
+        _ => break,
+      }
+    }
+
+    Ok(commits)
+  }
+
+  /// Forks of every repo in `repos`; neither repository listing counts them
+  async fn count_forks(&self, repos: &[Value]) -> u32 {
+    let endpoints: Vec<String> = repos
+      .iter()
+      .filter_map(|repo| match self.flavor {
+        Flavor::Cloud => repo["full_name"]
+          .as_str()
+          .map(|name| format!("/repositories/{}/forks?pagelen=1", name)),
+        Flavor::DataCenter => Some(format!(
+          "/projects/{}/repos/{}/forks?limit=100",
+          repo["project"]["key"].as_str()?,
+          repo["slug"].as_str()?
+        )),
+      })
+      .collect();
+
+    stream::iter(endpoints)
+      .map(|endpoint| async move {
+        match self.flavor {
+          Flavor::Cloud => self.api_request(&endpoint).await.ok()?["size"].as_u64(),
+          Flavor::DataCenter => {
+            let forks = self.data_center_values(&endpoint).await.ok()?;
+            Some(forks.len() as u64)
+          }
+        }
+      })
+      .buffer_unordered(languages::CONCURRENT_REQUESTS)
+      .filter_map(|count| async move { count })
+      .fold(0, |total, count| async move { total + count as u32 })
+      .await
+  }
+
+  async fn fetch_cloud_repos(&self, username: &str) -> Result<Vec<Value>> {
+    // Cloud pages link to the next page with an absolute "next" URL
+    let mut repos = Vec::new();
+    let mut next = Some(format!("/repositories/{}?pagelen=100", username));
+
+    while let Some(endpoint) = next {
+      let data = self.api_request(&endpoint).await?;
+
+      if let Some(values) = data["values"].as_array() {
+        repos.extend(values.iter().cloned());
+      }
+
+      next = data["next"].as_str().map(String::from);
+    }
+
+    Ok(repos)
+  }
+
+  async fn fetch_data_center_repos(&self, username: &str) -> Result<Vec<Value>> {
+    // Personal repositories live in the user's "~slug" project
+    self
+      .data_center_values(&format!("/projects/~{}/repos?limit=100", username))
+      .await
+  }
+
+  /// Every value of a paged Data Center listing. Its `size` only counts the
+  /// current page, so pages are followed until `isLastPage`.
+  async fn data_center_values(&self, endpoint: &str) -> Result<Vec<Value>> {
+    let mut values = Vec::new();
+    let mut start = 0;
+
+    loop {
+      let data = self
+        .api_request(&format!("{}&start={}", endpoint, start))
+        .await?;
+
+      if let Some(page) = data["values"].as_array() {
+        values.extend(page.iter().cloned());
+      }
+
+      match data["nextPageStart"].as_u64() {
+        Some(next) if !data["isLastPage"].as_bool().unwrap_or(true) => start = next,
+        _ => break,
+      }
+    }
+
+    Ok(values)
+  }
+
+  async fn dashboard_items(&self, role: &str, filter: &str, per_page: usize) -> SearchResult {
+    let endpoint = format!(
+      "/dashboard/pull-requests?state=OPEN&role={}{}&limit=100",
+      role, filter
+    );
+
+    let values = match self.data_center_values(&endpoint).await {
+      Ok(values) => values,
+      Err(_) => return SearchResult::default(),
+    };
+
+    let items = values
+      .iter()
+      .take(per_page)
+      .map(data_center_pr_item)
+      .collect();
+
+    SearchResult {
+      total_count: values.len() as u32,
+      items,
+    }
+  }
+
+  async fn cloud_authored_items(&self, username: &str, per_page: usize) -> SearchResult {
+    let endpoint = format!("/pullrequests/{}?state=OPEN&pagelen=50", username);
+
+    let data = match self.api_request(&endpoint).await {
+      Ok(data) => data,
+      Err(_) => return SearchResult::default(),
+    };
+
+    let values = data["values"].as_array().cloned().unwrap_or_default();
+    let items = values.iter().take(per_page).map(cloud_pr_item).collect();
+
+    SearchResult {
+      total_count: data["size"].as_u64().unwrap_or(values.len() as u64) as u32,
+      items,
+    }
+  }
+}
+
+fn cloud_pr_item(pr: &Value) -> SearchItem {
+  SearchItem {
+    title: pr["title"].as_str().unwrap_or("").to_string(),
+    repo: pr["destination"]["repository"]["full_name"]
+      .as_str()
+      .unwrap_or("")
+      .to_string(),
+    url: pr["links"]["html"]["href"]
+      .as_str()
+      .unwrap_or("")
+      .to_string(),
+    number: pr["id"].as_i64().unwrap_or(0),
+    origin: None,
+  }
+}
+
+fn data_center_pr_item(pr: &Value) -> SearchItem {
+  let repo = &pr["toRef"]["repository"];
+  SearchItem {
+    title: pr["title"].as_str().unwrap_or("").to_string(),
+    repo: format!(
+      "{}/{}",
+      repo["project"]["key"].as_str().unwrap_or(""),
+      repo["slug"].as_str().unwrap_or("")
+    ),
+    url: pr["links"]["self"][0]["href"]
+      .as_str()
+      .unwrap_or("")
+      .to_string(),
+    number: pr["id"].as_i64().unwrap_or(0),
+    origin: None,
+  }
+}
+
+/// Cloud commit authors are "Name <email>" unless linked to an account
+fn cloud_commit_author(commit: &Value) -> Option<String> {
+  let author = &commit["author"];
+  author["user"]["nickname"]
+    .as_str()
+    .map(String::from)
+    .or_else(|| {
+      author["raw"]
+        .as_str()
+        .map(|raw| raw.split(" <").next().unwrap_or(raw).trim().to_string())
+    })
+}
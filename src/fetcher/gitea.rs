@@ -1,12 +1,22 @@
-use super::Fetcher;
+use super::{repo, FetchOptions, Fetcher};
+use crate::display::graph::ContributionGraph;
+use crate::models::{
+  ContributionWeek, IssueStats, PullRequestStats, RepoStats, SearchItem, SearchResult, UserData,
+  UserStats,
+};
+use crate::utils::calendar::{self, DateRange};
+use crate::utils::languages;
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate};
 use serde_json::Value;
+use std::collections::HashMap;
 
 pub struct GiteaFetcher {
   client: reqwest::Client,
   api_base: String,
   token: Option<String>,
+  options: FetchOptions,
 }
 
 impl GiteaFetcher {
@@ -18,10 +28,20 @@ impl GiteaFetcher {
       client: reqwest::Client::new(),
       api_base,
       token: token.map(String::from),
+      options: FetchOptions::default(),
     })
   }
 
-  fn api_request(&self, endpoint: &str) -> Result<Value> {
+  pub fn with_options(mut self, options: FetchOptions) -> Self {
+    self.options = options;
+    self
+  }
+
+  async fn api_request(&self, endpoint: &str) -> Result<Value> {
+    Ok(self.send(endpoint).await?.json::<Value>().await?)
+  }
+
+  async fn send(&self, endpoint: &str) -> Result<reqwest::Response> {
     let url = format!("{}{}", self.api_base, endpoint);
 
     let mut req = self.client.get(&url);
@@ -30,9 +50,10 @@ impl GiteaFetcher {
       req = req.header("Authorization", format!("token {}", token));
     }
 
-    let rt = tokio::runtime::Runtime::new()?;
-    let response =
-      rt.block_on(async { req.timeout(std::time::Duration::from_secs(30)).send().await })?;
+    let response = req
+      .timeout(std::time::Duration::from_secs(30))
+      .send()
+      .await?;
 
     if !response.status().is_success() {
       return Err(anyhow::anyhow!(
@@ -41,10 +62,7 @@ impl GiteaFetcher {
       ));
     }
 
-    let rt = tokio::runtime::Runtime::new()?;
-    let data = rt.block_on(async { response.json::<Value>().await })?;
-
-    Ok(data)
+    Ok(response)
   }
 }
 
@@ -55,24 +73,131 @@ impl Fetcher for GiteaFetcher {
       return Err(anyhow::anyhow!("Token required for Gitea authentication"));
     }
 
-    let data = self.api_request("/user")?;
+    let data = self.api_request("/user").await?;
     data["login"]
       .as_str()
       .map(String::from)
       .ok_or_else(|| anyhow::anyhow!("Could not get authenticated user"))
   }
 
-  async fn fetch_user_data(&self, username: &str) -> Result<Value> {
-    self.api_request(&format!("/users/{}", username))
-  }
+  async fn fetch_user_data(&self, username: &str) -> Result<UserData> {
+    let user = self.api_request(&format!("/users/{}", username)).await?;
 
-  async fn fetch_user_stats(&self, username: &str, user_data: Option<&Value>) -> Result<Value> {
-    let _user = if let Some(data) = user_data {
-      data.clone()
-    } else {
-      self.fetch_user_data(username).await?
+    let text = |key: &str| {
+      user[key]
+        .as_str()
+        .filter(|s| !s.is_empty())
+        .map(String::from)
     };
 
+    Ok(UserData {
+      login: text("login").unwrap_or_else(|| username.to_string()),
+      id: user["id"].as_u64(),
+      name: text("full_name"),
+      bio: text("description"),
+      blog: text("website"),
+      location: text("location"),
+      email: text("email"),
+      followers: user["followers_count"].as_u64().unwrap_or(0) as u32,
+      following: user["following_count"].as_u64().unwrap_or(0) as u32,
+      created_at: text("created"),
+      ..Default::default()
+    })
+  }
+
+  async fn fetch_repo_stats(&self, repo: &str) -> Result<RepoStats> {
+    repo::split_repo(repo)?;
+    let data = self.api_request(&format!("/repos/{}", repo)).await?;
+    let full_name = data["full_name"].as_str().unwrap_or(repo).to_string();
+
+    let languages = self
+      .api_request(&format!("/repos/{}/languages", full_name))
+      .await
+      .map(|langs| languages::from_breakdowns([(&langs, 1.0)]))
+      .unwrap_or_default();
+
+    // Gitea has no contributor statistics, so both come from recent commits
+    let range = repo::activity_range(self.options.range);
+    let commits = self
+      .fetch_commits(&full_name, range)
+      .await
+      .unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to fetch commit activity: {}", e);
+        Vec::new()
+      });
+    let commit_activity = repo::commit_calendar(
+      commits.iter().filter_map(|c| {
+        c["commit"]["author"]["date"]
+          .as_str()
+          .and_then(repo::commit_date)
+      }),
+      range,
+    );
+    let contributors = repo::top_contributors(commits.iter().filter_map(|c| {
+      c["author"]["login"]
+        .as_str()
+        .or_else(|| c["commit"]["author"]["name"].as_str())
+        .map(String::from)
+    }));
+
+    Ok(RepoStats {
+      description: data["description"]
+        .as_str()
+        .filter(|d| !d.is_empty())
+        .map(String::from),
+      url: data["html_url"].as_str().map(String::from),
+      stars: data["stars_count"].as_u64().unwrap_or(0) as u32,
+      forks: data["forks_count"].as_u64().unwrap_or(0) as u32,
+      languages,
+      commit_activity,
+      contributors,
+      pull_requests: self
+        .list_items(&format!("/repos/{}/pulls?state=open", full_name), 5)
+        .await,
+      issues: self
+        .list_items(
+          &format!("/repos/{}/issues?state=open&type=issues", full_name),
+          5,
+        )
+        .await,
+      full_name,
+    })
+  }
+
+  async fn fetch_org_members(&self, org: &str) -> Result<Vec<String>> {
+    let mut members = Vec::new();
+    let mut page = 1;
+    let per_page = 50;
+
+    loop {
+      let endpoint = format!("/orgs/{}/members?page={}&limit={}", org, page, per_page);
+      let data = self.api_request(&endpoint).await?;
+
+      let data_array = match data.as_array() {
+        Some(arr) if !arr.is_empty() => arr,
+        _ => break,
+      };
+
+      members.extend(
+        data_array
+          .iter()
+          .filter_map(|m| m["login"].as_str().map(String::from)),
+      );
+      page += 1;
+
+      if data_array.len() < per_page {
+        break;
+      }
+    }
+
+    Ok(members)
+  }
+
+  async fn fetch_user_stats(
+    &self,
+    username: &str,
+    _user_data: Option<&UserData>,
+  ) -> Result<UserStats> {
     // Fetch user's repositories
     let mut repos = Vec::new();
     let mut page = 1;
@@ -80,7 +205,7 @@ impl Fetcher for GiteaFetcher {
 
     loop {
       let endpoint = format!("/users/{}/repos?page={}&limit={}", username, page, per_page);
-      let data = self.api_request(&endpoint)?;
+      let data = self.api_request(&endpoint).await?;
 
       let data_array = match data.as_array() {
         Some(arr) if !arr.is_empty() => arr,
@@ -96,74 +221,218 @@ impl Fetcher for GiteaFetcher {
     }
 
     // Calculate statistics
-    let total_stars: i64 = repos.iter().filter_map(|r| r["stars_count"].as_i64()).sum();
+    let total_stars: u64 = repos.iter().filter_map(|r| r["stars_count"].as_u64()).sum();
 
-    let total_forks: i64 = repos.iter().filter_map(|r| r["forks_count"].as_i64()).sum();
+    let total_forks: u64 = repos.iter().filter_map(|r| r["forks_count"].as_u64()).sum();
 
     // Calculate language statistics
-    let languages = self.calculate_language_stats(&repos);
-
-    // Gitea doesn't have contribution graphs
-    Ok(serde_json::json!({
-      "total_stars": total_stars,
-      "total_forks": total_forks,
-      "total_repos": repos.len(),
-      "languages": languages,
-      "contribution_graph": [],
-      "current_streak": 0,
-      "longest_streak": 0,
-      "total_contributions": 0,
-      "pull_requests": {
-        "open": 0,
-        "awaiting_review": 0,
-        "mentions": 0
-      },
-      "issues": {
-        "assigned": 0,
-        "created": 0,
-        "mentions": 0
-      },
-    }))
+    let this = self;
+    let requests = repos
+      .iter()
+      .filter_map(|r| r["full_name"].as_str())
+      .map(|name| (format!("/repos/{}/languages", name), 1.0))
+      .collect();
+    let language_totals = languages::language_stats(
+      &repos,
+      self.options.language_weighting,
+      requests,
+      move |endpoint| async move { this.api_request(&endpoint).await },
+    )
+    .await;
+
+    let contrib_graph = match self.fetch_contribution_graph(username).await {
+      Ok(graph) => graph,
+      Err(e) => {
+        eprintln!("Warning: Failed to fetch contribution graph: {}", e);
+        Vec::new()
+      }
+    };
+
+    let graph = ContributionGraph::from_weeks(&contrib_graph);
+    let (current_streak, longest_streak) = graph.calculate_streaks();
+    let total_contributions = graph.calculate_total_contributions();
+
+    // The issue search filters only apply to the token owner
+    let is_self = self.is_authenticated_user(username).await;
+
+    let pull_requests = PullRequestStats {
+      awaiting_review: self
+        .search_items("type=pulls&review_requested=true", is_self, 5)
+        .await,
+      open: self
+        .search_items("type=pulls&created=true", is_self, 5)
+        .await,
+      mentions: self
+        .search_items("type=pulls&mentioned=true", is_self, 5)
+        .await,
+    };
+
+    let issues = IssueStats {
+      assigned: self
+        .search_items("type=issues&assigned=true", is_self, 5)
+        .await,
+      created: self
+        .search_items("type=issues&created=true", is_self, 5)
+        .await,
+      mentions: self
+        .search_items("type=issues&mentioned=true", is_self, 5)
+        .await,
+    };
+
+    Ok(UserStats {
+      total_stars: total_stars as u32,
+      total_forks: total_forks as u32,
+      total_repos: repos.len() as u32,
+      languages: languages::percentages(language_totals.clone()),
+      language_totals,
+      contribution_graph: contrib_graph,
+      current_streak,
+      longest_streak,
+      total_contributions,
+      pull_requests,
+      issues,
+      ..Default::default()
+    })
   }
 }
 
 impl GiteaFetcher {
-  fn calculate_language_stats(&self, repos: &[Value]) -> Value {
-    use std::collections::HashMap;
+  async fn fetch_contribution_graph(&self, username: &str) -> Result<Vec<ContributionWeek>> {
+    // The heatmap only reaches back a year; older days would read as zeros
+    let range = self.options.range.unwrap_or_default();
+    let earliest = DateRange::default().start;
+    if range.start < earliest {
+      return Err(anyhow::anyhow!(
+        "Gitea only serves the last year of contributions; start the range on or after {}",
+        earliest
+      ));
+    }
+
+    // Heatmap buckets are {timestamp, contributions}; fold them into days
+    let data = self
+      .api_request(&format!("/users/{}/heatmap", username))
+      .await?;
 
-    let mut language_counts: HashMap<String, i32> = HashMap::new();
+    let mut counts: HashMap<NaiveDate, u32> = HashMap::new();
+    for bucket in data.as_array().into_iter().flatten() {
+      let date = bucket["timestamp"]
+        .as_i64()
+        .and_then(|ts| DateTime::from_timestamp(ts, 0))
+        .map(|dt| dt.date_naive());
+      let contributions = bucket["contributions"].as_u64().unwrap_or(0) as u32;
 
-    for repo in repos {
-      if let Some(language) = repo["language"].as_str() {
-        if !language.is_empty() {
-          let normalized = language.to_lowercase();
-          *language_counts.entry(normalized).or_insert(0) += 1;
-        }
+      if let Some(date) = date {
+        *counts.entry(date).or_insert(0) += contributions;
       }
     }
 
-    let total: i32 = language_counts.values().sum();
-    if total == 0 {
-      return serde_json::json!({});
+    Ok(calendar::weeks_from_daily_counts(
+      &counts,
+      range.start,
+      range.end,
+    ))
+  }
+
+  async fn is_authenticated_user(&self, username: &str) -> bool {
+    if self.token.is_none() {
+      return false;
+    }
+
+    match self.api_request("/user").await {
+      Ok(user) => user["login"]
+        .as_str()
+        .is_some_and(|login| login.eq_ignore_ascii_case(username)),
+      Err(_) => false,
+    }
+  }
+
+  async fn fetch_commits(&self, repo: &str, range: DateRange) -> Result<Vec<Value>> {
+    let since = range.start.format("%Y-%m-%dT00:00:00Z");
+    let until = range.end.format("%Y-%m-%dT23:59:59Z");
+    let mut commits = Vec::new();
+    let per_page = 50;
+
+    for page in 1..=repo::MAX_COMMIT_PAGES {
+      let endpoint = format!(
+        "/repos/{}/commits?since={}&until={}&stat=false&page={}&limit={}",
+        repo, since, until, page, per_page
+      );
+      let data = self.api_request(&endpoint).await?;
+
+      let data_array = match data.as_array() {
+        Some(arr) if !arr.is_empty() => arr,
+        _ => break,
+      };
+
+      commits.extend(data_array.clone());
+
+      if data_array.len() < per_page {
+        break;
+      }
     }
 
-    let mut language_percentages: HashMap<String, f64> = HashMap::new();
-    for (lang, count) in language_counts {
-      let percentage = (count as f64 / total as f64) * 100.0;
-      let display_name = lang
-        .chars()
-        .enumerate()
-        .map(|(i, c)| {
-          if i == 0 {
-            c.to_uppercase().to_string()
-          } else {
-            c.to_string()
-          }
-        })
-        .collect::<String>();
-      language_percentages.insert(display_name, percentage);
+    Ok(commits)
+  }
+
+  async fn search_items(&self, filter: &str, is_self: bool, per_page: usize) -> SearchResult {
+    // Search issues and PRs of the authenticated user
+    if !is_self {
+      return SearchResult::default();
     }
 
-    serde_json::to_value(language_percentages).unwrap_or_else(|_| serde_json::json!({}))
+    self
+      .list_items(
+        &format!("/repos/issues/search?state=open&{}", filter),
+        per_page,
+      )
+      .await
+  }
+
+  /// First `per_page` issues or pull requests of a listing endpoint;
+  /// X-Total-Count carries the full count
+  async fn list_items(&self, endpoint: &str, per_page: usize) -> SearchResult {
+    let empty = SearchResult::default();
+    let endpoint = format!("{}&limit={}", endpoint, per_page);
+
+    let response = match self.send(&endpoint).await {
+      Ok(response) => response,
+      Err(_) => return empty,
+    };
+
+    let total = response
+      .headers()
+      .get("x-total-count")
+      .and_then(|v| v.to_str().ok())
+      .and_then(|v| v.parse::<u64>().ok());
+
+    let data: Value = match response.json().await {
+      Ok(data) => data,
+      Err(_) => return empty,
+    };
+
+    let items: Vec<SearchItem> = data
+      .as_array()
+      .map(|arr| {
+        arr
+          .iter()
+          .take(per_page)
+          .map(|item| SearchItem {
+            title: item["title"].as_str().unwrap_or("").to_string(),
+            repo: item["repository"]["full_name"]
+              .as_str()
+              .unwrap_or("")
+              .to_string(),
+            url: item["html_url"].as_str().unwrap_or("").to_string(),
+            number: item["number"].as_i64().unwrap_or(0),
+            origin: None,
+          })
+          .collect()
+      })
+      .unwrap_or_default();
+
+    SearchResult {
+      total_count: total.unwrap_or(items.len() as u64) as u32,
+      items,
+    }
   }
 }
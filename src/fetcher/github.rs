@@ -1,231 +1,592 @@
-use super::Fetcher;
+use super::{repo, FetchOptions, Fetcher};
+use crate::config::LanguageWeighting;
+use crate::display::graph::ContributionGraph;
+use crate::models::{
+  ContributionWeek, Contributor, IssueStats, PullRequestStats, RepoStats, SearchItem, SearchResult,
+  UserData, UserStats,
+};
+use crate::utils::calendar::{self, DateRange};
+use crate::utils::languages;
 use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::process::Command;
 
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
 pub struct GitHubFetcher {
-  _client: reqwest::Client,
+  client: reqwest::Client,
+  api_base: String,
+  graphql_url: String,
+  token: Option<String>,
+  options: FetchOptions,
 }
 
 impl GitHubFetcher {
-  pub fn new() -> Result<Self> {
-    // gh CLI 검증을 실제 사용 시점으로 지연 (Python과 동일)
+  pub fn new(base_url: &str, token: Option<&str>) -> Result<Self> {
+    let (api_base, graphql_url) = resolve_api_urls(base_url);
+    let token = resolve_token(token, &api_base);
+
     Ok(Self {
-      _client: reqwest::Client::new(),
+      client: reqwest::Client::new(),
+      api_base,
+      graphql_url,
+      token,
+      options: FetchOptions::default(),
     })
   }
 
-  fn gh_api(&self, endpoint: &str) -> Result<Value> {
-    let output = Command::new("gh").args(&["api", endpoint]).output()?;
+  pub fn with_options(mut self, options: FetchOptions) -> Self {
+    self.options = options;
+    self
+  }
 
-    if !output.status.success() {
-      let stderr = String::from_utf8_lossy(&output.stderr);
-      return Err(anyhow::anyhow!("gh api failed: {}", stderr));
+  fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+    let mut req = self
+      .client
+      .request(method, url)
+      .header("User-Agent", "gitfetch-rs")
+      .header("Accept", "application/vnd.github+json")
+      .timeout(std::time::Duration::from_secs(30));
+
+    if let Some(token) = &self.token {
+      req = req.header("Authorization", format!("Bearer {}", token));
     }
 
-    let stdout = String::from_utf8(output.stdout)?;
-    let data: Value = serde_json::from_str(&stdout)?;
-    Ok(data)
+    req
+  }
+
+  async fn api_request(&self, endpoint: &str) -> Result<Value> {
+    let url = format!("{}{}", self.api_base, endpoint);
+    let response = self.request(reqwest::Method::GET, &url).send().await?;
+
+    if !response.status().is_success() {
+      return Err(anyhow::anyhow!(
+        "GitHub API request failed: {}",
+        response.status()
+      ));
+    }
+
+    Ok(response.json::<Value>().await?)
   }
 
-  fn gh_graphql(&self, query: &str) -> Result<Value> {
-    let output = Command::new("gh")
-      .args(&["api", "graphql", "-f", &format!("query={}", query)])
-      .output()?;
+  async fn graphql_request(&self, query: &str, variables: Value) -> Result<Value> {
+    if self.token.is_none() {
+      return Err(anyhow::anyhow!("Token required for GitHub GraphQL API"));
+    }
+
+    let body = serde_json::json!({
+      "query": query,
+      "variables": variables,
+    });
+
+    let response = self
+      .request(reqwest::Method::POST, &self.graphql_url)
+      .json(&body)
+      .send()
+      .await?;
+
+    if !response.status().is_success() {
+      return Err(anyhow::anyhow!(
+        "GitHub GraphQL request failed: {}",
+        response.status()
+      ));
+    }
 
-    if !output.status.success() {
-      return Ok(serde_json::json!({}));
+    let data: Value = response.json().await?;
+
+    if let Some(message) = data["errors"]
+      .as_array()
+      .and_then(|errors| errors.first())
+      .and_then(|e| e["message"].as_str())
+    {
+      return Err(anyhow::anyhow!("GitHub GraphQL error: {}", message));
     }
 
-    let stdout = String::from_utf8(output.stdout)?;
-    let data: Value = serde_json::from_str(&stdout)?;
     Ok(data)
   }
 }
 
+/// Work out the REST and GraphQL endpoints from a configured instance URL.
+///
+/// github.com (and any `api.*` host) serves REST at the root and GraphQL at
+/// `/graphql`. GitHub Enterprise Server serves REST under `/api/v3` and
+/// GraphQL at `/api/graphql`, so both `https://ghe.corp` and
+/// `https://ghe.corp/api/v3` resolve to the same pair.
+pub fn resolve_api_urls(base_url: &str) -> (String, String) {
+  let base = base_url.trim().trim_end_matches('/');
+  let host = base
+    .split_once("://")
+    .map(|(_, rest)| rest)
+    .unwrap_or(base)
+    .split('/')
+    .next()
+    .unwrap_or("")
+    .to_lowercase();
+
+  if base.is_empty() || host == "github.com" || host == "www.github.com" {
+    return (
+      DEFAULT_API_URL.to_string(),
+      format!("{}/graphql", DEFAULT_API_URL),
+    );
+  }
+
+  if host.starts_with("api.") {
+    return (base.to_string(), format!("{}/graphql", base));
+  }
+
+  let root = base
+    .strip_suffix("/api/v3")
+    .or_else(|| base.strip_suffix("/api/graphql"))
+    .or_else(|| base.strip_suffix("/api"))
+    .unwrap_or(base);
+
+  (format!("{}/api/v3", root), format!("{}/api/graphql", root))
+}
+
+/// Resolve the GitHub token: explicit config value first, then the
+/// environment, then `gh auth token` if the GitHub CLI happens to be
+/// installed. github.com credentials (`GITHUB_TOKEN`, gh's default host)
+/// are never sent to an Enterprise Server, which uses
+/// `GH_ENTERPRISE_TOKEN` and gh's login for that host instead.
+fn resolve_token(token: Option<&str>, api_base: &str) -> Option<String> {
+  if let Some(token) = token.filter(|t| !t.is_empty()) {
+    return Some(token.to_string());
+  }
+
+  let host = api_base
+    .split_once("://")
+    .map(|(_, rest)| rest)
+    .unwrap_or(api_base)
+    .split('/')
+    .next()
+    .unwrap_or("");
+  let (variable, gh_args) = if api_base == DEFAULT_API_URL {
+    ("GITHUB_TOKEN", vec!["auth", "token"])
+  } else {
+    (
+      "GH_ENTERPRISE_TOKEN",
+      vec!["auth", "token", "--hostname", host],
+    )
+  };
+
+  if let Ok(token) = std::env::var(variable) {
+    if !token.is_empty() {
+      return Some(token);
+    }
+  }
+
+  let output = Command::new("gh").args(gh_args).output().ok()?;
+  if !output.status.success() {
+    return None;
+  }
+
+  let token = String::from_utf8(output.stdout).ok()?.trim().to_string();
+  if token.is_empty() {
+    None
+  } else {
+    Some(token)
+  }
+}
+
+/// Most days one `contributionsCollection` query may span
+const MAX_RANGE_DAYS: i64 = 365;
+
+/// Variables for the contribution calendar queries. A range is sent as
+/// `from`/`to` timestamps covering its first and last days in UTC.
+fn range_variables(range: Option<DateRange>) -> Value {
+  let (from, to) = match range {
+    Some(range) => (
+      Some(format!("{}T00:00:00Z", range.start)),
+      Some(format!("{}T23:59:59Z", range.end)),
+    ),
+    None => (None, None),
+  };
+
+  serde_json::json!({ "from": from, "to": to })
+}
+
 #[async_trait]
 impl Fetcher for GitHubFetcher {
   async fn get_authenticated_user(&self) -> Result<String> {
-    let data = self.gh_api("/user")?;
+    if self.token.is_none() {
+      return Err(anyhow::anyhow!("Token required for GitHub authentication"));
+    }
+
+    let data = self.api_request("/user").await?;
     data["login"]
       .as_str()
       .map(String::from)
       .ok_or_else(|| anyhow::anyhow!("Could not get authenticated user"))
   }
 
-  async fn fetch_user_data(&self, username: &str) -> Result<Value> {
-    self.gh_api(&format!("/users/{}", username))
+  async fn fetch_user_data(&self, username: &str) -> Result<UserData> {
+    let data = self.api_request(&format!("/users/{}", username)).await?;
+    Ok(serde_json::from_value(data)?)
   }
 
-  async fn fetch_user_stats(&self, username: &str, _user_data: Option<&Value>) -> Result<Value> {
-    // Fetch all public repositories (matching Python behavior)
-    let repos = self.fetch_repos(username)?;
-
-    let total_stars: i64 = repos
+  async fn fetch_repo_stats(&self, repo: &str) -> Result<RepoStats> {
+    repo::split_repo(repo)?;
+    // Commit activity statistics only cover the last year
+    let range = repo::activity_range(self.options.range);
+    if range.start < DateRange::default().start {
+      return Err(anyhow::anyhow!(
+        "GitHub only reports repository activity for the last year; pick a range after {}",
+        DateRange::default().start
+      ));
+    }
+    let data = self.api_request(&format!("/repos/{}", repo)).await?;
+    let full_name = data["full_name"].as_str().unwrap_or(repo).to_string();
+
+    let languages = self
+      .api_request(&format!("/repos/{}/languages", full_name))
+      .await
+      .map(|langs| languages::from_breakdowns([(&langs, 1.0)]))
+      .unwrap_or_default();
+
+    let contributors = self
+      .api_request(&format!(
+        "/repos/{}/contributors?per_page={}",
+        full_name,
+        repo::CONTRIBUTOR_LIMIT
+      ))
+      .await
+      .ok()
+      .and_then(|list| list.as_array().cloned())
+      .unwrap_or_default()
       .iter()
-      .filter_map(|r| r["stargazers_count"].as_i64())
-      .sum();
-    let total_forks: i64 = repos.iter().filter_map(|r| r["forks_count"].as_i64()).sum();
-
-    // Calculate language statistics
-    let languages = self.calculate_language_stats(&repos);
+      .map(|c| Contributor {
+        login: c["login"].as_str().unwrap_or("").to_string(),
+        contributions: c["contributions"].as_u64().unwrap_or(0) as u32,
+      })
+      .collect();
 
-    let contrib_graph = match self.fetch_contribution_graph(username) {
-      Ok(graph) => graph,
+    let commit_activity = match self.fetch_commit_activity(&full_name, range).await {
+      Ok(weeks) => weeks,
       Err(e) => {
-        eprintln!("Warning: Failed to fetch contribution graph: {}", e);
-        serde_json::json!([])
+        eprintln!("Warning: Failed to fetch commit activity: {}", e);
+        Vec::new()
       }
     };
 
-    let (current_streak, longest_streak, total_contributions) =
-      self.calculate_contribution_stats(&contrib_graph);
+    Ok(RepoStats {
+      description: data["description"].as_str().map(String::from),
+      url: data["html_url"].as_str().map(String::from),
+      stars: data["stargazers_count"].as_u64().unwrap_or(0) as u32,
+      forks: data["forks_count"].as_u64().unwrap_or(0) as u32,
+      languages,
+      commit_activity,
+      contributors,
+      pull_requests: self
+        .search_items(&format!("repo:{} is:pr state:open", full_name), 5)
+        .await,
+      issues: self
+        .search_items(&format!("repo:{} is:issue state:open", full_name), 5)
+        .await,
+      full_name,
+    })
+  }
 
-    // Get search username (@me for authenticated user, otherwise username)
-    let search_username = self.get_search_username(username);
+  async fn fetch_contribution_calendar(
+    &self,
+    user: &UserData,
+    range: DateRange,
+  ) -> Result<Vec<ContributionWeek>> {
+    Ok(self.fetch_calendar(&user.login, Some(range)).await?.0)
+  }
 
-    // Fetch PR and issue statistics
-    let pull_requests = serde_json::json!({
-        "awaiting_review": self.search_items(&format!("is:pr state:open review-requested:{}", search_username), 5),
-        "open": self.search_items(&format!("is:pr state:open author:{}", search_username), 5),
-        "mentions": self.search_items(&format!("is:pr state:open mentions:{}", search_username), 5),
-    });
+  async fn fetch_org_members(&self, org: &str) -> Result<Vec<String>> {
+    let mut members = Vec::new();
+    let mut page = 1;
+    let per_page = 100;
 
-    let issues = serde_json::json!({
-        "assigned": self.search_items(&format!("is:issue state:open assignee:{}", search_username), 5),
-        "created": self.search_items(&format!("is:issue state:open author:{}", search_username), 5),
-        "mentions": self.search_items(&format!("is:issue state:open mentions:{}", search_username), 5),
-    });
+    loop {
+      let endpoint = format!("/orgs/{}/members?page={}&per_page={}", org, page, per_page);
+      let data = self.api_request(&endpoint).await?;
 
-    Ok(serde_json::json!({
-        "total_stars": total_stars,
-        "total_forks": total_forks,
-        "total_repos": repos.len(),
-        "contribution_graph": contrib_graph,
-        "current_streak": current_streak,
-        "longest_streak": longest_streak,
-        "total_contributions": total_contributions,
-        "languages": languages,
-        "pull_requests": pull_requests,
-        "issues": issues,
-    }))
-  }
-}
+      let data_array = match data.as_array() {
+        Some(arr) if !arr.is_empty() => arr,
+        _ => break,
+      };
 
-impl GitHubFetcher {
-  fn fetch_contribution_graph(&self, username: &str) -> Result<Value> {
-    // GraphQL query for contribution calendar (matching Python behavior)
-    // Always use user(login: "...") - does NOT include private contributions
-    let query = format!(
-      r#"{{
-        user(login: "{}") {{
-          contributionsCollection {{
-            contributionCalendar {{
-              weeks {{
-                contributionDays {{
-                  contributionCount
-                  date
-                }}
-              }}
-            }}
-          }}
-        }}
-      }}"#,
-      username
-    );
+      members.extend(
+        data_array
+          .iter()
+          .filter_map(|m| m["login"].as_str().map(String::from)),
+      );
+      page += 1;
 
-    let data = self.gh_graphql(&query)?;
-    let path = &data["data"]["user"]["contributionsCollection"]["contributionCalendar"]["weeks"];
+      if data_array.len() < per_page {
+        break;
+      }
+    }
 
-    Ok(path.clone())
+    Ok(members)
   }
 
-  fn calculate_contribution_stats(&self, graph: &Value) -> (u32, u32, u32) {
-    let weeks = graph.as_array();
-    if weeks.is_none() {
-      return (0, 0, 0);
-    }
+  async fn fetch_user_stats(
+    &self,
+    username: &str,
+    _user_data: Option<&UserData>,
+  ) -> Result<UserStats> {
+    // Fetch all public repositories (matching Python behavior)
+    let repos = self.fetch_repos(username).await?;
 
-    let mut all_contributions: Vec<u32> = weeks
-      .unwrap()
+    let total_stars: u64 = repos
+      .iter()
+      .filter_map(|r| r["stargazers_count"].as_u64())
+      .sum();
+    let total_forks: u64 = repos.iter().filter_map(|r| r["forks_count"].as_u64()).sum();
+
+    // Calculate language statistics
+    let this = self;
+    let requests = repos
       .iter()
-      .flat_map(|w| w["contributionDays"].as_array())
-      .flatten()
-      .filter_map(|d| d["contributionCount"].as_u64())
-      .map(|c| c as u32)
+      .filter_map(|r| r["full_name"].as_str())
+      .map(|name| (format!("/repos/{}/languages", name), 1.0))
       .collect();
+    // Without a token only 60 requests an hour are allowed, too few for
+    // one per repository
+    let weighting = match self.token {
+      Some(_) => self.options.language_weighting,
+      None => LanguageWeighting::Repos,
+    };
+    let language_totals =
+      languages::language_stats(&repos, weighting, requests, move |endpoint| async move {
+        this.api_request(&endpoint).await
+      })
+      .await;
+
+    let (contrib_graph, private_contributions) =
+      match self.fetch_calendar(username, self.options.range).await {
+        Ok(calendar) => calendar,
+        Err(e) => {
+          eprintln!("Warning: Failed to fetch contribution graph: {}", e);
+          (Vec::new(), None)
+        }
+      };
+    if self.options.include_private && private_contributions.is_none() {
+      eprintln!("Warning: Private contributions are only available for the token owner");
+    }
 
-    all_contributions.reverse();
+    let graph = ContributionGraph::from_weeks(&contrib_graph);
+    let (current_streak, longest_streak) = graph.calculate_streaks();
+    let total_contributions = graph.calculate_total_contributions();
 
-    let total: u32 = all_contributions.iter().sum();
+    // Get search username (@me for authenticated user, otherwise username)
+    let search_username = self.get_search_username(username).await;
 
-    let mut current_streak = 0;
-    for &count in &all_contributions {
-      if count > 0 {
-        current_streak += 1;
-      } else {
-        break;
-      }
-    }
+    // Fetch PR and issue statistics
+    let pull_requests = PullRequestStats {
+      awaiting_review: self
+        .search_items(
+          &format!("is:pr state:open review-requested:{}", search_username),
+          5,
+        )
+        .await,
+      open: self
+        .search_items(&format!("is:pr state:open author:{}", search_username), 5)
+        .await,
+      mentions: self
+        .search_items(&format!("is:pr state:open mentions:{}", search_username), 5)
+        .await,
+    };
 
-    let mut longest_streak = 0;
-    let mut temp_streak = 0;
-    for &count in &all_contributions {
-      if count > 0 {
-        temp_streak += 1;
-        longest_streak = longest_streak.max(temp_streak);
-      } else {
-        temp_streak = 0;
-      }
-    }
+    let issues = IssueStats {
+      assigned: self
+        .search_items(
+          &format!("is:issue state:open assignee:{}", search_username),
+          5,
+        )
+        .await,
+      created: self
+        .search_items(
+          &format!("is:issue state:open author:{}", search_username),
+          5,
+        )
+        .await,
+      mentions: self
+        .search_items(
+          &format!("is:issue state:open mentions:{}", search_username),
+          5,
+        )
+        .await,
+    };
 
-    (current_streak, longest_streak, total)
+    Ok(UserStats {
+      total_stars: total_stars as u32,
+      total_forks: total_forks as u32,
+      total_repos: repos.len() as u32,
+      contribution_graph: contrib_graph,
+      current_streak,
+      longest_streak,
+      total_contributions,
+      languages: languages::percentages(language_totals.clone()),
+      language_totals,
+      pull_requests,
+      issues,
+      private_contributions,
+      ..Default::default()
+    })
   }
+}
+
+impl GitHubFetcher {
+  async fn fetch_commit_activity(
+    &self,
+    repo: &str,
+    range: DateRange,
+  ) -> Result<Vec<ContributionWeek>> {
+    // Weekly buckets of per-day commit counts for the last year. GitHub
+    // answers 202 with an empty body while it computes them.
+    let data = self
+      .api_request(&format!("/repos/{}/stats/commit_activity", repo))
+      .await?;
+    let weeks = data.as_array().ok_or_else(|| {
+      anyhow::anyhow!("GitHub is still computing commit activity, try again shortly")
+    })?;
+
+    let dates = weeks.iter().flat_map(|week| {
+      let start = chrono::DateTime::from_timestamp(week["week"].as_i64().unwrap_or(0), 0)
+        .map(|d| d.date_naive());
+      week["days"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter_map(move |(offset, count)| {
+          let date = start? + chrono::Duration::days(offset as i64);
+          Some(std::iter::repeat_n(
+            date,
+            count.as_u64().unwrap_or(0) as usize,
+          ))
+        })
+        .flatten()
+    });
 
-  fn calculate_language_stats(&self, repos: &[Value]) -> Value {
-    use std::collections::HashMap;
+    Ok(repo::commit_calendar(dates, range))
+  }
 
-    // First pass: collect language occurrences
-    let mut language_counts: HashMap<String, i32> = HashMap::new();
+  /// Contribution calendar for `username`. With `include_private` and the
+  /// token owner as `username`, private activity is included and the count
+  /// of restricted contributions is returned alongside.
+  async fn fetch_calendar(
+    &self,
+    username: &str,
+    range: Option<DateRange>,
+  ) -> Result<(Vec<ContributionWeek>, Option<u32>)> {
+    let private = self.options.include_private && self.get_search_username(username).await == "@me";
+    let Some(range) = range else {
+      return self.fetch_calendar_chunk(username, None, private).await;
+    };
 
-    for repo in repos {
-      if let Some(language) = repo["language"].as_str() {
-        if !language.is_empty() {
-          let normalized = language.to_lowercase();
-          *language_counts.entry(normalized).or_insert(0) += 1;
-        }
+    // contributionsCollection spans at most a year, so longer ranges are
+    // fetched piecewise and stitched back together
+    let mut counts = HashMap::new();
+    let mut restricted = None;
+    for chunk in range.chunks(MAX_RANGE_DAYS) {
+      let (weeks, chunk_restricted) = self
+        .fetch_calendar_chunk(username, Some(chunk), private)
+        .await?;
+      counts.extend(
+        calendar::daily_counts(&weeks)
+          .into_iter()
+          .filter(|(date, _)| chunk.contains(*date)),
+      );
+      if let Some(count) = chunk_restricted {
+        *restricted.get_or_insert(0) += count;
       }
     }
 
-    // Calculate percentages
-    let total: i32 = language_counts.values().sum();
-    if total == 0 {
-      return serde_json::json!({});
+    Ok((
+      calendar::weeks_from_daily_counts(&counts, range.start, range.end),
+      restricted,
+    ))
+  }
+
+  async fn fetch_calendar_chunk(
+    &self,
+    username: &str,
+    range: Option<DateRange>,
+    private: bool,
+  ) -> Result<(Vec<ContributionWeek>, Option<u32>)> {
+    if private {
+      let (weeks, restricted) = self.fetch_viewer_contribution_graph(range).await?;
+      return Ok((weeks, Some(restricted)));
     }
 
-    let mut language_percentages: HashMap<String, f64> = HashMap::new();
-    for (lang, count) in language_counts {
-      let percentage = (count as f64 / total as f64) * 100.0;
-      // Capitalize first letter
-      let display_name = lang
-        .chars()
-        .enumerate()
-        .map(|(i, c)| {
-          if i == 0 {
-            c.to_uppercase().to_string()
-          } else {
-            c.to_string()
+    Ok((self.fetch_contribution_graph(username, range).await?, None))
+  }
+
+  async fn fetch_contribution_graph(
+    &self,
+    username: &str,
+    range: Option<DateRange>,
+  ) -> Result<Vec<ContributionWeek>> {
+    // GraphQL query for contribution calendar (matching Python behavior)
+    // user(login: "...") does NOT include private contributions; see
+    // fetch_viewer_contribution_graph. Null from/to leave GitHub's default
+    // rolling year.
+    let query = r#"
+      query($login: String!, $from: DateTime, $to: DateTime) {
+        user(login: $login) {
+          contributionsCollection(from: $from, to: $to) {
+            contributionCalendar {
+              weeks {
+                contributionDays {
+                  contributionCount
+                  date
+                }
+              }
+            }
           }
-        })
-        .collect::<String>();
-      language_percentages.insert(display_name, percentage);
-    }
+        }
+      }"#;
+
+    let mut variables = range_variables(range);
+    variables["login"] = Value::from(username);
+    let data = self.graphql_request(query, variables).await?;
+    let path = &data["data"]["user"]["contributionsCollection"]["contributionCalendar"]["weeks"];
+
+    Ok(serde_json::from_value(path.clone()).unwrap_or_default())
+  }
 
-    serde_json::to_value(language_percentages).unwrap_or_else(|_| serde_json::json!({}))
+  /// The token owner's calendar including private contributions, and how
+  /// many of them are restricted
+  async fn fetch_viewer_contribution_graph(
+    &self,
+    range: Option<DateRange>,
+  ) -> Result<(Vec<ContributionWeek>, u32)> {
+    let query = r#"
+      query($from: DateTime, $to: DateTime) {
+        viewer {
+          contributionsCollection(from: $from, to: $to) {
+            restrictedContributionsCount
+            contributionCalendar {
+              weeks {
+                contributionDays {
+                  contributionCount
+                  date
+                }
+              }
+            }
+          }
+        }
+      }"#;
+
+    let data = self.graphql_request(query, range_variables(range)).await?;
+    let collection = &data["data"]["viewer"]["contributionsCollection"];
+    let weeks = serde_json::from_value(collection["contributionCalendar"]["weeks"].clone())
+      .unwrap_or_default();
+    let restricted = collection["restrictedContributionsCount"]
+      .as_u64()
+      .unwrap_or(0) as u32;
+
+    Ok((weeks, restricted))
   }
 
-  fn fetch_repos(&self, username: &str) -> Result<Vec<Value>> {
+  async fn fetch_repos(&self, username: &str) -> Result<Vec<Value>> {
     // Always fetch public repositories only (matching Python gitfetch behavior)
     // This uses /users/{username}/repos which only returns public repos
     let mut repos = Vec::new();
@@ -237,7 +598,7 @@ impl GitHubFetcher {
         "/users/{}/repos?page={}&per_page={}&type=owner&sort=updated",
         username, page, per_page
       );
-      let data = self.gh_api(&endpoint)?;
+      let data = self.api_request(&endpoint).await?;
 
       let data_array = match data.as_array() {
         Some(arr) if !arr.is_empty() => arr,
@@ -255,144 +616,72 @@ impl GitHubFetcher {
     Ok(repos)
   }
 
-  fn get_search_username(&self, username: &str) -> String {
+  async fn get_search_username(&self, username: &str) -> String {
     // Get the username to use for search queries
     // Uses @me for the authenticated user, otherwise the provided username
-    match self.gh_api("/user") {
-      Ok(auth_user) => {
-        if let Some(login) = auth_user["login"].as_str() {
-          if login == username {
-            return "@me".to_string();
-          }
+    if self.token.is_none() {
+      return username.to_string();
+    }
+
+    if let Ok(auth_user) = self.api_request("/user").await {
+      if let Some(login) = auth_user["login"].as_str() {
+        if login.eq_ignore_ascii_case(username) {
+          return "@me".to_string();
         }
       }
-      Err(_) => {
-        // If we can't determine auth user, use provided username
-      }
     }
+
     username.to_string()
   }
 
-  fn search_items(&self, query: &str, per_page: usize) -> Value {
-    // Search issues and PRs using GitHub CLI search command
-    let search_type = if query.contains("is:pr") {
-      "prs"
-    } else {
-      "issues"
-    };
-
-    // Remove is:pr/issue from query as it's implied by search type
-    let cleaned_query = query.replace("is:pr ", "").replace("is:issue ", "");
-
-    // Parse query string and convert to command-line flags
-    let flags = self.parse_search_query(&cleaned_query);
-
-    // Build command
-    let mut cmd = Command::new("gh");
-    cmd.arg("search").arg(search_type);
-
-    for flag in flags {
-      cmd.arg(flag);
-    }
-
-    cmd.args(&[
-      "--limit",
-      &per_page.to_string(),
-      "--json",
-      "number,title,repository,url,state",
-    ]);
-
-    let output = match cmd.output() {
-      Ok(out) if out.status.success() => out,
-      _ => {
-        return serde_json::json!({"total_count": 0, "items": []});
-      }
-    };
-
-    let stdout = match String::from_utf8(output.stdout) {
-      Ok(s) => s,
-      Err(_) => {
-        return serde_json::json!({"total_count": 0, "items": []});
-      }
-    };
-
-    let data: Vec<Value> = match serde_json::from_str(&stdout) {
-      Ok(d) => d,
-      Err(_) => {
-        return serde_json::json!({"total_count": 0, "items": []});
-      }
+  async fn search_items(&self, query: &str, per_page: usize) -> SearchResult {
+    // Search issues and PRs using the REST search API
+    let empty = SearchResult::default();
+
+    let url = format!("{}/search/issues", self.api_base);
+    let response = self
+      .request(reqwest::Method::GET, &url)
+      .query(&[("q", query), ("per_page", &per_page.to_string())])
+      .send()
+      .await;
+
+    let data: Value = match response {
+      Ok(resp) if resp.status().is_success() => match resp.json().await {
+        Ok(data) => data,
+        Err(_) => return empty,
+      },
+      _ => return empty,
     };
 
     // Extract relevant fields from items
-    let items: Vec<Value> = data
-      .iter()
-      .take(per_page)
+    let items: Vec<SearchItem> = data["items"]
+      .as_array()
+      .map(|arr| arr.iter().take(per_page).collect::<Vec<_>>())
+      .unwrap_or_default()
+      .into_iter()
       .map(|item| {
-        let repo_info = item.get("repository").and_then(|r| r.as_object());
-        let repo_name = repo_info
-          .and_then(|r| r.get("nameWithOwner"))
-          .or_else(|| repo_info.and_then(|r| r.get("name")))
-          .and_then(|n| n.as_str())
+        // repository_url looks like https://api.github.com/repos/{owner}/{name}
+        let repo_name = item["repository_url"]
+          .as_str()
+          .and_then(|u| u.split_once("/repos/"))
+          .map(|(_, name)| name)
           .unwrap_or("");
 
-        serde_json::json!({
-          "title": item.get("title").and_then(|t| t.as_str()).unwrap_or(""),
-          "repo": repo_name,
-          "url": item.get("url").and_then(|u| u.as_str()).unwrap_or(""),
-          "number": item.get("number").and_then(|n| n.as_i64()).unwrap_or(0),
-        })
+        SearchItem {
+          title: item["title"].as_str().unwrap_or("").to_string(),
+          repo: repo_name.to_string(),
+          url: item["html_url"].as_str().unwrap_or("").to_string(),
+          number: item["number"].as_i64().unwrap_or(0),
+          origin: None,
+        }
       })
       .collect();
 
-    serde_json::json!({
-      "total_count": items.len(),
-      "items": items
-    })
-  }
+    let total_count = data["total_count"].as_u64().unwrap_or(items.len() as u64);
 
-  fn parse_search_query(&self, query: &str) -> Vec<String> {
-    // Parse search query string into command-line flags
-    let mut flags = Vec::new();
-    let parts: Vec<&str> = query.split_whitespace().collect();
-
-    for part in parts {
-      if let Some((key, value)) = part.split_once(':') {
-        match key {
-          "assignee" => {
-            flags.push("--assignee".to_string());
-            flags.push(value.to_string());
-          }
-          "author" => {
-            flags.push("--author".to_string());
-            flags.push(value.to_string());
-          }
-          "mentions" => {
-            flags.push("--mentions".to_string());
-            flags.push(value.to_string());
-          }
-          "review-requested" => {
-            flags.push("--review-requested".to_string());
-            flags.push(value.to_string());
-          }
-          "state" => {
-            flags.push("--state".to_string());
-            flags.push(value.to_string());
-          }
-          "is" => {
-            // is:pr and is:issue are handled by search type
-            // Skip this
-          }
-          _ => {
-            // For other qualifiers, add as search term
-            flags.push(part.to_string());
-          }
-        }
-      } else {
-        // Add as general search term
-        flags.push(part.to_string());
-      }
+    SearchResult {
+      total_count: total_count as u32,
+      items,
     }
-
-    flags
   }
 }
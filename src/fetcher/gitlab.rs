@@ -1,12 +1,22 @@
-use super::Fetcher;
+use super::{repo, FetchOptions, Fetcher};
+use crate::display::graph::ContributionGraph;
+use crate::models::{
+  ContributionWeek, Contributor, IssueStats, PullRequestStats, RepoStats, SearchItem, SearchResult,
+  UserData, UserStats,
+};
+use crate::utils::calendar::{self, DateRange};
+use crate::utils::languages;
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use serde_json::Value;
+use std::collections::HashMap;
 
 pub struct GitLabFetcher {
   client: reqwest::Client,
   base_url: String,
   token: Option<String>,
+  options: FetchOptions,
 }
 
 impl GitLabFetcher {
@@ -15,21 +25,36 @@ impl GitLabFetcher {
       client: reqwest::Client::new(),
       base_url: base_url.trim_end_matches('/').to_string(),
       token: token.map(String::from),
+      options: FetchOptions::default(),
     })
   }
 
-  fn api_request(&self, endpoint: &str) -> Result<Value> {
-    let url = format!("{}/api/v4{}", self.base_url, endpoint);
+  pub fn with_options(mut self, options: FetchOptions) -> Self {
+    self.options = options;
+    self
+  }
+
+  async fn api_request(&self, endpoint: &str) -> Result<Value> {
+    self
+      .get_json(&format!("{}/api/v4{}", self.base_url, endpoint))
+      .await
+  }
+
+  async fn get_json(&self, url: &str) -> Result<Value> {
+    Ok(self.send(url).await?.json::<Value>().await?)
+  }
 
-    let mut req = self.client.get(&url);
+  async fn send(&self, url: &str) -> Result<reqwest::Response> {
+    let mut req = self.client.get(url);
 
     if let Some(token) = &self.token {
       req = req.header("PRIVATE-TOKEN", token);
     }
 
-    let rt = tokio::runtime::Runtime::new()?;
-    let response =
-      rt.block_on(async { req.timeout(std::time::Duration::from_secs(30)).send().await })?;
+    let response = req
+      .timeout(std::time::Duration::from_secs(30))
+      .send()
+      .await?;
 
     if !response.status().is_success() {
       return Err(anyhow::anyhow!(
@@ -38,10 +63,7 @@ impl GitLabFetcher {
       ));
     }
 
-    let rt = tokio::runtime::Runtime::new()?;
-    let data = rt.block_on(async { response.json::<Value>().await })?;
-
-    Ok(data)
+    Ok(response)
   }
 }
 
@@ -52,37 +74,169 @@ impl Fetcher for GitLabFetcher {
       return Err(anyhow::anyhow!("Token required for GitLab authentication"));
     }
 
-    let data = self.api_request("/user")?;
+    let data = self.api_request("/user").await?;
     data["username"]
       .as_str()
       .map(String::from)
       .ok_or_else(|| anyhow::anyhow!("Could not get authenticated user"))
   }
 
-  async fn fetch_user_data(&self, username: &str) -> Result<Value> {
+  async fn fetch_user_data(&self, username: &str) -> Result<UserData> {
     // Search for user by username
-    let users = self.api_request(&format!("/users?username={}", username))?;
+    let users = self
+      .api_request(&format!("/users?username={}", username))
+      .await?;
+
+    let user = users
+      .as_array()
+      .and_then(|arr| arr.first())
+      .ok_or_else(|| anyhow::anyhow!("User not found: {}", username))?;
+
+    let text = |key: &str| {
+      user[key]
+        .as_str()
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+    };
 
-    if let Some(user_array) = users.as_array() {
-      if let Some(user) = user_array.first() {
-        return Ok(user.clone());
+    Ok(UserData {
+      login: text("username").unwrap_or_else(|| username.to_string()),
+      id: user["id"].as_u64(),
+      name: text("name"),
+      bio: text("bio"),
+      company: text("organization"),
+      blog: text("website_url"),
+      location: text("location"),
+      email: text("public_email"),
+      followers: user["followers"].as_u64().unwrap_or(0) as u32,
+      following: user["following"].as_u64().unwrap_or(0) as u32,
+      created_at: text("created_at"),
+      ..Default::default()
+    })
+  }
+
+  async fn fetch_repo_stats(&self, repo: &str) -> Result<RepoStats> {
+    repo::split_repo(repo)?;
+    // Projects are addressed by their URL-encoded full path
+    let id = repo.trim_matches('/').replace('/', "%2F");
+    let project = self.api_request(&format!("/projects/{}", id)).await?;
+
+    // GitLab already reports languages as percentages
+    let languages = self
+      .api_request(&format!("/projects/{}/languages", id))
+      .await
+      .map(|langs| languages::from_breakdowns([(&langs, 1.0)]))
+      .unwrap_or_default();
+
+    let contributors = self
+      .api_request(&format!(
+        "/projects/{}/repository/contributors?order_by=commits&sort=desc&per_page={}",
+        id,
+        repo::CONTRIBUTOR_LIMIT
+      ))
+      .await
+      .ok()
+      .and_then(|list| list.as_array().cloned())
+      .unwrap_or_default()
+      .iter()
+      .map(|c| Contributor {
+        login: c["name"].as_str().unwrap_or("").to_string(),
+        contributions: c["commits"].as_u64().unwrap_or(0) as u32,
+      })
+      .collect();
+
+    let range = repo::activity_range(self.options.range);
+    let commit_activity = match self.fetch_commit_dates(&id, range).await {
+      Ok(dates) => repo::commit_calendar(dates, range),
+      Err(e) => {
+        eprintln!("Warning: Failed to fetch commit activity: {}", e);
+        Vec::new()
+      }
+    };
+
+    Ok(RepoStats {
+      full_name: project["path_with_namespace"]
+        .as_str()
+        .unwrap_or(repo)
+        .to_string(),
+      description: project["description"]
+        .as_str()
+        .filter(|d| !d.is_empty())
+        .map(String::from),
+      url: project["web_url"].as_str().map(String::from),
+      stars: project["star_count"].as_u64().unwrap_or(0) as u32,
+      forks: project["forks_count"].as_u64().unwrap_or(0) as u32,
+      languages,
+      commit_activity,
+      contributors,
+      pull_requests: self
+        .search_items(&format!("/projects/{}/merge_requests?state=opened", id), 5)
+        .await,
+      issues: self
+        .search_items(&format!("/projects/{}/issues?state=opened", id), 5)
+        .await,
+    })
+  }
+
+  async fn fetch_contribution_calendar(
+    &self,
+    user: &UserData,
+    range: DateRange,
+  ) -> Result<Vec<ContributionWeek>> {
+    let user_id = user.id.ok_or_else(|| anyhow::anyhow!("Invalid user ID"))?;
+    self
+      .fetch_contribution_graph(&user.login, user_id, range)
+      .await
+  }
+
+  async fn fetch_org_members(&self, org: &str) -> Result<Vec<String>> {
+    // Subgroups are addressed by their URL-encoded full path
+    let group = org.replace('/', "%2F");
+    let mut members = Vec::new();
+    let mut page = 1;
+    let per_page = 100;
+
+    loop {
+      let endpoint = format!(
+        "/groups/{}/members/all?page={}&per_page={}",
+        group, page, per_page
+      );
+      let data = self.api_request(&endpoint).await?;
+
+      let data_array = match data.as_array() {
+        Some(arr) if !arr.is_empty() => arr,
+        _ => break,
+      };
+
+      members.extend(
+        data_array
+          .iter()
+          .filter_map(|m| m["username"].as_str().map(String::from)),
+      );
+      page += 1;
+
+      if data_array.len() < per_page {
+        break;
       }
     }
 
-    Err(anyhow::anyhow!("User not found: {}", username))
+    Ok(members)
   }
 
-  async fn fetch_user_stats(&self, username: &str, user_data: Option<&Value>) -> Result<Value> {
-    let user = if let Some(data) = user_data {
-      data.clone()
-    } else {
-      self.fetch_user_data(username).await?
+  async fn fetch_user_stats(
+    &self,
+    username: &str,
+    user_data: Option<&UserData>,
+  ) -> Result<UserStats> {
+    let user_id = match user_data.and_then(|data| data.id) {
+      Some(id) => id,
+      None => self
+        .fetch_user_data(username)
+        .await?
+        .id
+        .ok_or_else(|| anyhow::anyhow!("Invalid user ID"))?,
     };
 
-    let user_id = user["id"]
-      .as_u64()
-      .ok_or_else(|| anyhow::anyhow!("Invalid user ID"))?;
-
     // Fetch user's projects
     let mut repos = Vec::new();
     let mut page = 1;
@@ -90,10 +244,10 @@ impl Fetcher for GitLabFetcher {
 
     loop {
       let endpoint = format!(
-        "/users/{}/projects?page={}&per_page={}",
+        "/users/{}/projects?statistics=true&page={}&per_page={}",
         user_id, page, per_page
       );
-      let data = self.api_request(&endpoint)?;
+      let data = self.api_request(&endpoint).await?;
 
       let data_array = match data.as_array() {
         Some(arr) if !arr.is_empty() => arr,
@@ -109,75 +263,323 @@ impl Fetcher for GitLabFetcher {
     }
 
     // Calculate statistics
-    let total_stars: i64 = repos.iter().filter_map(|r| r["star_count"].as_i64()).sum();
-
-    let total_forks: i64 = repos.iter().filter_map(|r| r["forks_count"].as_i64()).sum();
-
-    // Calculate language statistics
-    let languages = self.calculate_language_stats(&repos);
-
-    // GitLab doesn't have contribution graphs like GitHub
-    // Return simplified stats
-    Ok(serde_json::json!({
-      "total_stars": total_stars,
-      "total_forks": total_forks,
-      "total_repos": repos.len(),
-      "languages": languages,
-      "contribution_graph": [],
-      "current_streak": 0,
-      "longest_streak": 0,
-      "total_contributions": 0,
-      "pull_requests": {
-        "open": 0,
-        "awaiting_review": 0,
-        "mentions": 0
-      },
-      "issues": {
-        "assigned": 0,
-        "created": 0,
-        "mentions": 0
-      },
-    }))
+    let total_stars: u64 = repos.iter().filter_map(|r| r["star_count"].as_u64()).sum();
+
+    let total_forks: u64 = repos.iter().filter_map(|r| r["forks_count"].as_u64()).sum();
+
+    // GitLab reports each project's languages as percentages, so they are
+    // scaled by repository size when every project exposes it
+    let sizes: Option<Vec<f64>> = repos
+      .iter()
+      .map(|r| r["statistics"]["repository_size"].as_f64())
+      .collect();
+    let this = self;
+    let requests = repos
+      .iter()
+      .enumerate()
+      .filter_map(|(i, r)| {
+        let weight = sizes.as_ref().map_or(1.0, |sizes| sizes[i]);
+        r["id"]
+          .as_u64()
+          .map(|id| (format!("/projects/{}/languages", id), weight))
+      })
+      .collect();
+    let language_totals = languages::language_stats(
+      &repos,
+      self.options.language_weighting,
+      requests,
+      move |endpoint| async move { this.api_request(&endpoint).await },
+    )
+    .await;
+
+    let range = self.options.range.unwrap_or_default();
+    let contrib_graph = match self
+      .fetch_contribution_graph(username, user_id, range)
+      .await
+    {
+      Ok(graph) => graph,
+      Err(e) => {
+        eprintln!("Warning: Failed to fetch contribution graph: {}", e);
+        Vec::new()
+      }
+    };
+
+    let graph = ContributionGraph::from_weeks(&contrib_graph);
+    let (current_streak, longest_streak) = graph.calculate_streaks();
+    let total_contributions = graph.calculate_total_contributions();
+
+    // Mentions come from the todo list, which only exists for the token owner
+    let is_self = self.is_authenticated_user(username).await;
+
+    let pull_requests = PullRequestStats {
+      awaiting_review: self
+        .search_items(
+          &format!(
+            "/merge_requests?state=opened&scope=all&reviewer_id={}",
+            user_id
+          ),
+          5,
+        )
+        .await,
+      open: self
+        .search_items(
+          &format!(
+            "/merge_requests?state=opened&scope=all&author_id={}",
+            user_id
+          ),
+          5,
+        )
+        .await,
+      mentions: self.mention_items("MergeRequest", is_self, 5).await,
+    };
+
+    let issues = IssueStats {
+      assigned: self
+        .search_items(
+          &format!("/issues?state=opened&scope=all&assignee_id={}", user_id),
+          5,
+        )
+        .await,
+      created: self
+        .search_items(
+          &format!("/issues?state=opened&scope=all&author_id={}", user_id),
+          5,
+        )
+        .await,
+      mentions: self.mention_items("Issue", is_self, 5).await,
+    };
+
+    Ok(UserStats {
+      total_stars: total_stars as u32,
+      total_forks: total_forks as u32,
+      total_repos: repos.len() as u32,
+      languages: languages::percentages(language_totals.clone()),
+      language_totals,
+      contribution_graph: contrib_graph,
+      current_streak,
+      longest_streak,
+      total_contributions,
+      pull_requests,
+      issues,
+      ..Default::default()
+    })
   }
 }
 
 impl GitLabFetcher {
-  fn calculate_language_stats(&self, repos: &[Value]) -> Value {
-    use std::collections::HashMap;
+  async fn fetch_contribution_graph(
+    &self,
+    username: &str,
+    user_id: u64,
+    range: DateRange,
+  ) -> Result<Vec<ContributionWeek>> {
+    // The profile calendar is served by the web frontend, not /api/v4, and
+    // only covers the last year. Count events instead when it is unavailable
+    // (e.g. private profiles) or the range reaches further back.
+    let calendar = if range.start >= DateRange::default().start {
+      self.fetch_calendar(username).await.ok()
+    } else {
+      None
+    };
+    let counts = match calendar {
+      Some(counts) => counts,
+      None => self.fetch_event_counts(user_id, range).await?,
+    };
+
+    Ok(calendar::weeks_from_daily_counts(
+      &counts,
+      range.start,
+      range.end,
+    ))
+  }
+
+  async fn fetch_calendar(&self, username: &str) -> Result<HashMap<NaiveDate, u32>> {
+    let url = format!("{}/users/{}/calendar.json", self.base_url, username);
+    let data = self.get_json(&url).await?;
+
+    let days = data
+      .as_object()
+      .ok_or_else(|| anyhow::anyhow!("Unexpected calendar response"))?;
+
+    Ok(
+      days
+        .iter()
+        .filter_map(|(date, count)| {
+          let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+          Some((date, count.as_u64()? as u32))
+        })
+        .collect(),
+    )
+  }
 
-    let mut language_counts: HashMap<String, i32> = HashMap::new();
+  async fn fetch_event_counts(
+    &self,
+    user_id: u64,
+    range: DateRange,
+  ) -> Result<HashMap<NaiveDate, u32>> {
+    let mut counts = HashMap::new();
+    let mut page = 1;
+    let per_page = 100;
+
+    // `after` and `before` are exclusive, so widen them by a day each
+    let after = (range.start - chrono::Duration::days(1)).format("%Y-%m-%d");
+    let before = (range.end + chrono::Duration::days(1)).format("%Y-%m-%d");
 
-    for repo in repos {
-      if let Some(language) = repo["language"].as_str() {
-        if !language.is_empty() {
-          let normalized = language.to_lowercase();
-          *language_counts.entry(normalized).or_insert(0) += 1;
+    loop {
+      let endpoint = format!(
+        "/users/{}/events?after={}&before={}&page={}&per_page={}",
+        user_id, after, before, page, per_page
+      );
+      let data = self.api_request(&endpoint).await?;
+
+      let data_array = match data.as_array() {
+        Some(arr) if !arr.is_empty() => arr,
+        _ => break,
+      };
+
+      for event in data_array {
+        let date = event["created_at"]
+          .as_str()
+          .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+          .map(|dt| dt.date_naive());
+
+        if let Some(date) = date {
+          *counts.entry(date).or_insert(0) += 1;
         }
       }
+
+      page += 1;
+
+      if data_array.len() < per_page {
+        break;
+      }
     }
 
-    let total: i32 = language_counts.values().sum();
-    if total == 0 {
-      return serde_json::json!({});
+    Ok(counts)
+  }
+
+  async fn is_authenticated_user(&self, username: &str) -> bool {
+    if self.token.is_none() {
+      return false;
     }
 
-    let mut language_percentages: HashMap<String, f64> = HashMap::new();
-    for (lang, count) in language_counts {
-      let percentage = (count as f64 / total as f64) * 100.0;
-      let display_name = lang
-        .chars()
-        .enumerate()
-        .map(|(i, c)| {
-          if i == 0 {
-            c.to_uppercase().to_string()
-          } else {
-            c.to_string()
-          }
-        })
-        .collect::<String>();
-      language_percentages.insert(display_name, percentage);
+    match self.api_request("/user").await {
+      Ok(user) => user["username"]
+        .as_str()
+        .is_some_and(|login| login.eq_ignore_ascii_case(username)),
+      Err(_) => false,
     }
+  }
+
+  async fn fetch_commit_dates(&self, project_id: &str, range: DateRange) -> Result<Vec<NaiveDate>> {
+    let since = range.start.format("%Y-%m-%dT00:00:00Z");
+    let until = range.end.format("%Y-%m-%dT23:59:59Z");
+    let mut dates = Vec::new();
+    let per_page = 100;
+
+    for page in 1..=repo::MAX_COMMIT_PAGES {
+      let endpoint = format!(
+        "/projects/{}/repository/commits?since={}&until={}&page={}&per_page={}",
+        project_id, since, until, page, per_page
+      );
+      let data = self.api_request(&endpoint).await?;
 
-    serde_json::to_value(language_percentages).unwrap_or_else(|_| serde_json::json!({}))
+      let data_array = match data.as_array() {
+        Some(arr) if !arr.is_empty() => arr,
+        _ => break,
+      };
+
+      dates.extend(
+        data_array
+          .iter()
+          .filter_map(|c| c["committed_date"].as_str().and_then(repo::commit_date)),
+      );
+
+      if data_array.len() < per_page {
+        break;
+      }
+    }
+
+    Ok(dates)
+  }
+
+  async fn search_items(&self, endpoint: &str, per_page: usize) -> SearchResult {
+    // Search merge requests or issues; X-Total carries the full count
+    let url = format!("{}/api/v4{}&per_page={}", self.base_url, endpoint, per_page);
+
+    let (data, total) = match self.fetch_page(&url).await {
+      Ok(result) => result,
+      Err(_) => return SearchResult::default(),
+    };
+
+    let items: Vec<SearchItem> = data
+      .as_array()
+      .map(|arr| arr.iter().take(per_page).map(Self::to_item).collect())
+      .unwrap_or_default();
+
+    SearchResult {
+      total_count: total.unwrap_or(items.len() as u64) as u32,
+      items,
+    }
+  }
+
+  async fn mention_items(&self, target_type: &str, is_self: bool, per_page: usize) -> SearchResult {
+    if !is_self {
+      return SearchResult::default();
+    }
+
+    let url = format!(
+      "{}/api/v4/todos?state=pending&action=mentioned&type={}&per_page={}",
+      self.base_url, target_type, per_page
+    );
+
+    let (data, total) = match self.fetch_page(&url).await {
+      Ok(result) => result,
+      Err(_) => return SearchResult::default(),
+    };
+
+    let items: Vec<SearchItem> = data
+      .as_array()
+      .map(|arr| {
+        arr
+          .iter()
+          .take(per_page)
+          .map(|todo| Self::to_item(&todo["target"]))
+          .collect()
+      })
+      .unwrap_or_default();
+
+    SearchResult {
+      total_count: total.unwrap_or(items.len() as u64) as u32,
+      items,
+    }
+  }
+
+  async fn fetch_page(&self, url: &str) -> Result<(Value, Option<u64>)> {
+    let response = self.send(url).await?;
+    let total = response
+      .headers()
+      .get("x-total")
+      .and_then(|v| v.to_str().ok())
+      .and_then(|v| v.parse::<u64>().ok());
+
+    Ok((response.json::<Value>().await?, total))
+  }
+
+  fn to_item(item: &Value) -> SearchItem {
+    // references.full looks like "group/project!12" or "group/project#34"
+    let repo = item["references"]["full"]
+      .as_str()
+      .and_then(|r| r.rsplit_once(['!', '#']))
+      .map(|(repo, _)| repo)
+      .unwrap_or("");
+
+    SearchItem {
+      title: item["title"].as_str().unwrap_or("").to_string(),
+      repo: repo.to_string(),
+      url: item["web_url"].as_str().unwrap_or("").to_string(),
+      number: item["iid"].as_i64().unwrap_or(0),
+      origin: None,
+    }
   }
 }
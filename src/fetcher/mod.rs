@@ -1,29 +1,85 @@
+pub mod accounts;
+pub mod bitbucket;
 pub mod gitea;
 pub mod github;
 pub mod gitlab;
+pub mod repo;
 pub mod sourcehut;
 
+use crate::config::LanguageWeighting;
+use crate::models::{ContributionWeek, RepoStats, UserData, UserStats};
+use crate::utils::calendar::DateRange;
 use anyhow::Result;
 use async_trait::async_trait;
-use serde_json::Value;
+
+/// Settings that change what fetchers compute rather than where they fetch
+#[derive(Debug, Clone, Default)]
+pub struct FetchOptions {
+  pub language_weighting: LanguageWeighting,
+  /// Contribution calendar window; `None` is the provider's rolling year
+  pub range: Option<DateRange>,
+  /// Include private contributions when the user is the token owner
+  pub include_private: bool,
+}
 
 #[async_trait]
 pub trait Fetcher: Send + Sync {
   async fn get_authenticated_user(&self) -> Result<String>;
-  async fn fetch_user_data(&self, username: &str) -> Result<Value>;
-  async fn fetch_user_stats(&self, username: &str, user_data: Option<&Value>) -> Result<Value>;
+  async fn fetch_user_data(&self, username: &str) -> Result<UserData>;
+  async fn fetch_user_stats(
+    &self,
+    username: &str,
+    user_data: Option<&UserData>,
+  ) -> Result<UserStats>;
+
+  /// Card data for one repository, addressed as `owner/name`
+  async fn fetch_repo_stats(&self, repo: &str) -> Result<RepoStats>;
+
+  /// Contribution calendar for `user` over an arbitrary range, for views
+  /// that need more than the profile's calendar
+  async fn fetch_contribution_calendar(
+    &self,
+    user: &UserData,
+    range: DateRange,
+  ) -> Result<Vec<ContributionWeek>> {
+    let _ = range;
+    Err(anyhow::anyhow!(
+      "Calendars for past years of {} are not supported by this provider",
+      user.login
+    ))
+  }
+
+  /// Logins of every member of an organization or group
+  async fn fetch_org_members(&self, org: &str) -> Result<Vec<String>> {
+    Err(anyhow::anyhow!(
+      "Listing members of {} is not supported by this provider",
+      org
+    ))
+  }
 }
 
 pub fn create_fetcher(
   provider: &str,
   base_url: &str,
   token: Option<&str>,
+  options: &FetchOptions,
 ) -> Result<Box<dyn Fetcher>> {
   match provider {
-    "github" => Ok(Box::new(github::GitHubFetcher::new()?)),
-    "gitlab" => Ok(Box::new(gitlab::GitLabFetcher::new(base_url, token)?)),
-    "gitea" => Ok(Box::new(gitea::GiteaFetcher::new(base_url, token)?)),
-    "sourcehut" => Ok(Box::new(sourcehut::SourcehutFetcher::new(base_url, token)?)),
+    "github" => Ok(Box::new(
+      github::GitHubFetcher::new(base_url, token)?.with_options(options.clone()),
+    )),
+    "gitlab" => Ok(Box::new(
+      gitlab::GitLabFetcher::new(base_url, token)?.with_options(options.clone()),
+    )),
+    "gitea" => Ok(Box::new(
+      gitea::GiteaFetcher::new(base_url, token)?.with_options(options.clone()),
+    )),
+    "sourcehut" => Ok(Box::new(
+      sourcehut::SourcehutFetcher::new(base_url, token)?.with_options(options.clone()),
+    )),
+    "bitbucket" => Ok(Box::new(
+      bitbucket::BitbucketFetcher::new(base_url, token)?.with_options(options.clone()),
+    )),
     _ => Err(anyhow::anyhow!("Unsupported provider: {}", provider)),
   }
 }
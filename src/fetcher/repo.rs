@@ -0,0 +1,72 @@
+use crate::models::{ContributionWeek, Contributor};
+use crate::utils::calendar::{self, DateRange};
+use anyhow::Result;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+/// How many committers a repository card lists
+pub const CONTRIBUTOR_LIMIT: usize = 5;
+
+/// Upper bound on commit pages walked for providers without an activity
+/// endpoint, so very busy repositories don't take hundreds of requests
+pub const MAX_COMMIT_PAGES: usize = 10;
+
+/// Splits `owner/name`; the owner may itself contain slashes (GitLab subgroups)
+pub fn split_repo(repo: &str) -> Result<(&str, &str)> {
+  repo
+    .trim_matches('/')
+    .rsplit_once('/')
+    .filter(|(owner, name)| !owner.is_empty() && !name.is_empty())
+    .ok_or_else(|| anyhow::anyhow!("Expected a repository as owner/name, got '{}'", repo))
+}
+
+/// Window commit activity is collected for: the requested range, or the
+/// default rolling year
+pub fn activity_range(range: Option<DateRange>) -> DateRange {
+  range.unwrap_or_default()
+}
+
+/// Parses the RFC 3339 timestamps providers use for commit dates
+pub fn commit_date(timestamp: &str) -> Option<NaiveDate> {
+  chrono::DateTime::parse_from_rfc3339(timestamp)
+    .ok()
+    .map(|date| date.date_naive())
+}
+
+/// Calendar of commits per day over `range`; commits outside it are dropped
+pub fn commit_calendar(
+  dates: impl IntoIterator<Item = NaiveDate>,
+  range: DateRange,
+) -> Vec<ContributionWeek> {
+  let mut counts: HashMap<NaiveDate, u32> = HashMap::new();
+
+  for date in dates {
+    *counts.entry(date).or_insert(0) += 1;
+  }
+
+  calendar::weeks_from_daily_counts(&counts, range.start, range.end)
+}
+
+/// Tallies commit authors, busiest first
+pub fn top_contributors(authors: impl IntoIterator<Item = String>) -> Vec<Contributor> {
+  let mut counts: HashMap<String, u32> = HashMap::new();
+  for author in authors {
+    *counts.entry(author).or_insert(0) += 1;
+  }
+
+  let mut contributors: Vec<Contributor> = counts
+    .into_iter()
+    .map(|(login, contributions)| Contributor {
+      login,
+      contributions,
+    })
+    .collect();
+  contributors.sort_by(|a, b| {
+    b.contributions
+      .cmp(&a.contributions)
+      .then_with(|| a.login.cmp(&b.login))
+  });
+  contributors.truncate(CONTRIBUTOR_LIMIT);
+
+  contributors
+}
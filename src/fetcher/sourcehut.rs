@@ -1,12 +1,101 @@
-use super::Fetcher;
+use super::{repo, FetchOptions, Fetcher};
+use crate::models::{
+  IssueStats, PullRequestStats, RepoStats, SearchItem, SearchResult, UserData, UserStats,
+};
 use anyhow::Result;
 use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
 use serde_json::Value;
 
+/// Ticket states that still count as open on todo.sr.ht
+const OPEN_TICKET_STATUSES: [&str; 4] = ["REPORTED", "CONFIRMED", "IN_PROGRESS", "PENDING"];
+
+/// Patchset states that still count as open on lists.sr.ht
+const OPEN_PATCHSET_STATUSES: [&str; 3] = ["UNKNOWN", "PROPOSED", "NEEDS_REVISION"];
+
+/// Upper bound on pages walked per tracker or list listing. Neither takes a
+/// status filter, so closed items come along and old ones are cut off here;
+/// results are newest first, so the cut-off ones are mostly closed.
+const MAX_PAGES: usize = 10;
+
+/// Ticket fields shared by the tracker and ticket page queries
+const TICKET_FIELDS: &str = "
+  fragment ticket on Ticket {
+    id
+    subject
+    status
+    submitter { canonicalName }
+    assignees { canonicalName }
+  }";
+
+/// Patchset fields shared by the list and patch page queries
+const PATCHSET_FIELDS: &str = "
+  fragment patchset on Patchset {
+    id
+    subject
+    status
+    submitter { canonicalName }
+  }";
+
+/// A page of a GraphQL listing; `cursor` is set while more pages follow
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+  #[serde(default = "Vec::new")]
+  results: Vec<T>,
+  cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entity {
+  #[serde(default)]
+  canonical_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tracker {
+  name: String,
+  tickets: Page<Ticket>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Ticket {
+  id: i64,
+  subject: String,
+  status: String,
+  submitter: Option<Entity>,
+  #[serde(default)]
+  assignees: Vec<Entity>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MailingList {
+  name: String,
+  patches: Page<Patchset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Patchset {
+  id: i64,
+  subject: String,
+  status: String,
+  submitter: Option<Entity>,
+}
+
+/// An open ticket or patchset with the people it belongs to
+#[derive(Debug)]
+struct WorkItem {
+  item: SearchItem,
+  submitter: String,
+  assignees: Vec<String>,
+}
+
 pub struct SourcehutFetcher {
   client: reqwest::Client,
   base_url: String,
   token: Option<String>,
+  options: FetchOptions,
 }
 
 impl SourcehutFetcher {
@@ -15,21 +104,51 @@ impl SourcehutFetcher {
       client: reqwest::Client::new(),
       base_url: base_url.trim_end_matches('/').to_string(),
       token: token.map(String::from),
+      options: FetchOptions::default(),
     })
   }
 
-  fn api_request(&self, endpoint: &str) -> Result<Value> {
-    let url = format!("{}/api{}", self.base_url, endpoint);
+  pub fn with_options(mut self, options: FetchOptions) -> Self {
+    self.options = options;
+    self
+  }
 
-    let mut req = self.client.get(&url);
+  /// Each sr.ht service lives on its own host (meta.sr.ht, git.sr.ht, ...).
+  /// The configured URL normally points at git.sr.ht, so swap the service
+  /// prefix; any other URL is treated as a single host serving every API.
+  fn service_url(&self, service: &str) -> String {
+    let (scheme, host) = self
+      .base_url
+      .split_once("://")
+      .unwrap_or(("https", &self.base_url));
 
-    if let Some(token) = &self.token {
-      req = req.header("Authorization", format!("token {}", token));
-    }
+    ["git.", "meta.", "todo.", "lists."]
+      .iter()
+      .find_map(|prefix| host.strip_prefix(prefix))
+      .map(|domain| format!("{}://{}.{}", scheme, service, domain))
+      .unwrap_or_else(|| self.base_url.clone())
+  }
+
+  async fn graphql_request(&self, service: &str, query: &str, variables: Value) -> Result<Value> {
+    let token = self
+      .token
+      .as_ref()
+      .ok_or_else(|| anyhow::anyhow!("Token required for Sourcehut GraphQL API"))?;
 
-    let rt = tokio::runtime::Runtime::new()?;
-    let response =
-      rt.block_on(async { req.timeout(std::time::Duration::from_secs(30)).send().await })?;
+    let url = format!("{}/query", self.service_url(service));
+    let body = serde_json::json!({
+      "query": query,
+      "variables": variables,
+    });
+
+    let response = self
+      .client
+      .post(&url)
+      .header("Authorization", format!("Bearer {}", token))
+      .json(&body)
+      .timeout(std::time::Duration::from_secs(30))
+      .send()
+      .await?;
 
     if !response.status().is_success() {
       return Err(anyhow::anyhow!(
@@ -38,8 +157,15 @@ impl SourcehutFetcher {
       ));
     }
 
-    let rt = tokio::runtime::Runtime::new()?;
-    let data = rt.block_on(async { response.json::<Value>().await })?;
+    let data: Value = response.json().await?;
+
+    if let Some(message) = data["errors"]
+      .as_array()
+      .and_then(|errors| errors.first())
+      .and_then(|e| e["message"].as_str())
+    {
+      return Err(anyhow::anyhow!("Sourcehut GraphQL error: {}", message));
+    }
 
     Ok(data)
   }
@@ -54,102 +180,476 @@ impl Fetcher for SourcehutFetcher {
       ));
     }
 
-    let data = self.api_request("/user/profile")?;
-    data["username"]
+    let data = self
+      .graphql_request("meta", "query { me { username } }", serde_json::json!({}))
+      .await?;
+    data["data"]["me"]["username"]
       .as_str()
       .map(String::from)
       .ok_or_else(|| anyhow::anyhow!("Could not get authenticated user"))
   }
 
-  async fn fetch_user_data(&self, username: &str) -> Result<Value> {
-    // Sourcehut doesn't have a direct user profile endpoint
-    // Return minimal user data
-    Ok(serde_json::json!({
-      "username": username,
-      "name": username,
-      "bio": "",
-      "website": "",
-      "company": "",
-    }))
+  async fn fetch_user_data(&self, username: &str) -> Result<UserData> {
+    let query = r#"
+      query($username: String!) {
+        userByName(username: $username) {
+          username
+          canonicalName
+          email
+          url
+          location
+          bio
+          created
+        }
+      }"#;
+
+    let data = self
+      .graphql_request(
+        "meta",
+        query,
+        serde_json::json!({ "username": normalize_username(username) }),
+      )
+      .await?;
+
+    let user = &data["data"]["userByName"];
+    if user.is_null() {
+      return Err(anyhow::anyhow!("User not found: {}", username));
+    }
+
+    let text = |key: &str| user[key].as_str().map(String::from);
+
+    Ok(UserData {
+      login: text("username").unwrap_or_else(|| normalize_username(username).to_string()),
+      name: text("canonicalName"),
+      bio: text("bio"),
+      blog: text("url"),
+      location: text("location"),
+      email: text("email"),
+      created_at: text("created"),
+      ..Default::default()
+    })
   }
 
-  async fn fetch_user_stats(&self, username: &str, _user_data: Option<&Value>) -> Result<Value> {
-    // Fetch user's repositories from git.sr.ht
-    let repos_endpoint = format!("/repos?owner={}", username);
-    let repos_data = self
-      .api_request(&repos_endpoint)
-      .unwrap_or_else(|_| serde_json::json!({"results": []}));
+  async fn fetch_repo_stats(&self, repo: &str) -> Result<RepoStats> {
+    let (owner, name) = repo::split_repo(repo)?;
+    let owner = normalize_username(owner);
 
-    let repos = repos_data["results"]
-      .as_array()
-      .cloned()
-      .unwrap_or_default();
-
-    // Calculate language statistics
-    let languages = self.calculate_language_stats(&repos);
-
-    // Sourcehut has minimal stats
-    Ok(serde_json::json!({
-      "total_stars": 0,
-      "total_forks": 0,
-      "total_repos": repos.len(),
-      "languages": languages,
-      "contribution_graph": [],
-      "current_streak": 0,
-      "longest_streak": 0,
-      "total_contributions": 0,
-      "pull_requests": {
-        "open": 0,
-        "awaiting_review": 0,
-        "mentions": 0
-      },
-      "issues": {
-        "assigned": 0,
-        "created": 0,
-        "mentions": 0
-      },
-    }))
+    let query = r#"
+      query($username: String!, $name: String!, $cursor: Cursor) {
+        user(username: $username) {
+          repository(name: $name) {
+            name
+            description
+            log(cursor: $cursor) {
+              results { author { name time } }
+              cursor
+            }
+          }
+        }
+      }"#;
+
+    // The log is newest first, so stop at the first page reaching past the window
+    let range = repo::activity_range(self.options.range);
+    let mut description = None;
+    let mut commits = Vec::new();
+    let mut cursor = Value::Null;
+
+    for _ in 0..repo::MAX_COMMIT_PAGES {
+      let data = self
+        .graphql_request(
+          "git",
+          query,
+          serde_json::json!({ "username": owner, "name": name, "cursor": cursor }),
+        )
+        .await?;
+
+      let repository = &data["data"]["user"]["repository"];
+      if repository.is_null() {
+        return Err(anyhow::anyhow!("Repository not found: {}", repo));
+      }
+      description = repository["description"]
+        .as_str()
+        .filter(|d| !d.is_empty())
+        .map(String::from);
+
+      let mut reached_end = false;
+      for commit in repository["log"]["results"]
+        .as_array()
+        .into_iter()
+        .flatten()
+      {
+        let author = &commit["author"];
+        let Some(date) = author["time"].as_str().and_then(repo::commit_date) else {
+          continue;
+        };
+        if date < range.start {
+          reached_end = true;
+          break;
+        }
+        if date > range.end {
+          continue;
+        }
+        commits.push((date, author["name"].as_str().unwrap_or("").to_string()));
+      }
+
+      cursor = repository["log"]["cursor"].clone();
+      if reached_end || cursor.is_null() {
+        break;
+      }
+    }
+
+    // git.sr.ht has no stars, forks or language statistics, and patches and
+    // tickets are not tied to a repository
+    Ok(RepoStats {
+      full_name: format!("~{}/{}", owner, name),
+      description,
+      url: Some(format!("{}/~{}/{}", self.service_url("git"), owner, name)),
+      commit_activity: repo::commit_calendar(commits.iter().map(|(date, _)| *date), range),
+      contributors: repo::top_contributors(commits.into_iter().map(|(_, author)| author)),
+      ..Default::default()
+    })
+  }
+
+  async fn fetch_user_stats(
+    &self,
+    username: &str,
+    _user_data: Option<&UserData>,
+  ) -> Result<UserStats> {
+    // There is no contribution calendar to narrow down
+    if self.options.range.is_some() {
+      return Err(anyhow::anyhow!(
+        "Sourcehut has no contribution calendar, so --year/--from/--to only apply to --repo"
+      ));
+    }
+    let username = normalize_username(username);
+    let canonical_name = format!("~{}", username);
+
+    let repos = self.fetch_repos(username).await?;
+
+    // Tickets on the user's trackers stand in for issues
+    let tickets = self.fetch_tickets(username).await.unwrap_or_else(|e| {
+      eprintln!("Warning: Failed to fetch todo.sr.ht tickets: {}", e);
+      Vec::new()
+    });
+
+    // Patchsets sent to the user's mailing lists stand in for pull requests
+    let patchsets = self.fetch_patchsets(username).await.unwrap_or_else(|e| {
+      eprintln!("Warning: Failed to fetch lists.sr.ht patches: {}", e);
+      Vec::new()
+    });
+
+    let is_own = |work: &&WorkItem| work.submitter == canonical_name;
+    let is_assigned = |work: &&WorkItem| work.assignees.contains(&canonical_name);
+
+    // sr.ht has no mention tracking, so mentions stay empty
+    let pull_requests = PullRequestStats {
+      awaiting_review: summarize(patchsets.iter().filter(|p| !is_own(p)), 5),
+      open: summarize(patchsets.iter().filter(is_own), 5),
+      mentions: SearchResult::default(),
+    };
+
+    let issues = IssueStats {
+      assigned: summarize(tickets.iter().filter(is_assigned), 5),
+      created: summarize(tickets.iter().filter(is_own), 5),
+      mentions: SearchResult::default(),
+    };
+
+    // git.sr.ht does not report repository languages or stars
+    Ok(UserStats {
+      total_repos: repos.len() as u32,
+      pull_requests,
+      issues,
+      ..Default::default()
+    })
   }
 }
 
 impl SourcehutFetcher {
-  fn calculate_language_stats(&self, repos: &[Value]) -> Value {
-    use std::collections::HashMap;
+  async fn fetch_repos(&self, username: &str) -> Result<Vec<Value>> {
+    let query = r#"
+      query($username: String!, $cursor: Cursor) {
+        user(username: $username) {
+          repositories(cursor: $cursor) {
+            results { name description visibility updated }
+            cursor
+          }
+        }
+      }"#;
+
+    let mut repos = Vec::new();
+    let mut cursor = Value::Null;
+
+    loop {
+      let data = self
+        .graphql_request(
+          "git",
+          query,
+          serde_json::json!({ "username": username, "cursor": cursor }),
+        )
+        .await?;
+
+      let page = &data["data"]["user"]["repositories"];
+      if let Some(results) = page["results"].as_array() {
+        repos.extend(results.iter().cloned());
+      }
+
+      cursor = page["cursor"].clone();
+      if cursor.is_null() {
+        break;
+      }
+    }
+
+    Ok(repos)
+  }
 
-    let mut language_counts: HashMap<String, i32> = HashMap::new();
+  /// The `data.user` listing at `pointer` (e.g. `/trackers`) of a GraphQL
+  /// response, deserialized
+  async fn user_page<T: DeserializeOwned>(
+    &self,
+    service: &str,
+    query: &str,
+    variables: Value,
+    pointer: &str,
+  ) -> Result<Page<T>> {
+    let username = variables["username"]
+      .as_str()
+      .unwrap_or_default()
+      .to_string();
+    let mut data = self.graphql_request(service, query, variables).await?;
+
+    let user = data["data"]["user"].take();
+    if user.is_null() {
+      return Err(anyhow::anyhow!("User not found: {}", username));
+    }
+    let page = user
+      .pointer(pointer)
+      .filter(|page| !page.is_null())
+      .ok_or_else(|| anyhow::anyhow!("Missing {} in Sourcehut response", pointer))?;
+    Ok(serde_json::from_value(page.clone())?)
+  }
 
-    for repo in repos {
-      // Sourcehut might not have language field, try to detect from repo data
-      if let Some(language) = repo["language"].as_str() {
-        if !language.is_empty() {
-          let normalized = language.to_lowercase();
-          *language_counts.entry(normalized).or_insert(0) += 1;
+  /// Trackers with their tickets, following both cursors up to `MAX_PAGES`
+  async fn fetch_trackers(&self, username: &str) -> Result<Vec<Tracker>> {
+    let query = r#"
+      query($username: String!, $cursor: Cursor) {
+        user(username: $username) {
+          trackers(cursor: $cursor) {
+            results {
+              name
+              tickets {
+                results { ...ticket }
+                cursor
+              }
+            }
+            cursor
+          }
+        }
+      }"#;
+    let tickets_query = r#"
+      query($username: String!, $name: String!, $cursor: Cursor) {
+        user(username: $username) {
+          tracker(name: $name) {
+            tickets(cursor: $cursor) {
+              results { ...ticket }
+              cursor
+            }
+          }
         }
+      }"#;
+
+    let mut trackers = Vec::new();
+    let mut cursor = None;
+
+    for _ in 0..MAX_PAGES {
+      let page: Page<Tracker> = self
+        .user_page(
+          "todo",
+          &format!("{}{}", query, TICKET_FIELDS),
+          serde_json::json!({ "username": username, "cursor": cursor }),
+          "/trackers",
+        )
+        .await?;
+
+      for mut tracker in page.results {
+        for _ in 1..MAX_PAGES {
+          let Some(next) = tracker.tickets.cursor.take() else {
+            break;
+          };
+          let more: Page<Ticket> = self
+            .user_page(
+              "todo",
+              &format!("{}{}", tickets_query, TICKET_FIELDS),
+              serde_json::json!({ "username": username, "name": tracker.name, "cursor": next }),
+              "/tracker/tickets",
+            )
+            .await?;
+          tracker.tickets.results.extend(more.results);
+          tracker.tickets.cursor = more.cursor;
+        }
+        trackers.push(tracker);
+      }
+
+      cursor = page.cursor;
+      if cursor.is_none() {
+        break;
       }
     }
 
-    let total: i32 = language_counts.values().sum();
-    if total == 0 {
-      return serde_json::json!({});
+    Ok(trackers)
+  }
+
+  async fn fetch_tickets(&self, username: &str) -> Result<Vec<WorkItem>> {
+    let base = self.service_url("todo");
+    let mut tickets = Vec::new();
+
+    for tracker in self.fetch_trackers(username).await? {
+      for ticket in tracker.tickets.results {
+        if !OPEN_TICKET_STATUSES.contains(&ticket.status.as_str()) {
+          continue;
+        }
+
+        tickets.push(WorkItem {
+          item: SearchItem {
+            title: ticket.subject,
+            repo: format!("~{}/{}", username, tracker.name),
+            url: format!("{}/~{}/{}/{}", base, username, tracker.name, ticket.id),
+            number: ticket.id,
+            origin: None,
+          },
+          submitter: ticket
+            .submitter
+            .map(|e| e.canonical_name)
+            .unwrap_or_default(),
+          assignees: ticket
+            .assignees
+            .into_iter()
+            .map(|assignee| assignee.canonical_name)
+            .collect(),
+        });
+      }
     }
 
-    let mut language_percentages: HashMap<String, f64> = HashMap::new();
-    for (lang, count) in language_counts {
-      let percentage = (count as f64 / total as f64) * 100.0;
-      let display_name = lang
-        .chars()
-        .enumerate()
-        .map(|(i, c)| {
-          if i == 0 {
-            c.to_uppercase().to_string()
-          } else {
-            c.to_string()
+    Ok(tickets)
+  }
+
+  /// Mailing lists with their patchsets, following both cursors up to
+  /// `MAX_PAGES`
+  async fn fetch_lists(&self, username: &str) -> Result<Vec<MailingList>> {
+    let query = r#"
+      query($username: String!, $cursor: Cursor) {
+        user(username: $username) {
+          lists(cursor: $cursor) {
+            results {
+              name
+              patches {
+                results { ...patchset }
+                cursor
+              }
+            }
+            cursor
+          }
+        }
+      }"#;
+    let patches_query = r#"
+      query($username: String!, $name: String!, $cursor: Cursor) {
+        user(username: $username) {
+          list(name: $name) {
+            patches(cursor: $cursor) {
+              results { ...patchset }
+              cursor
+            }
           }
-        })
-        .collect::<String>();
-      language_percentages.insert(display_name, percentage);
+        }
+      }"#;
+
+    let mut lists = Vec::new();
+    let mut cursor = None;
+
+    for _ in 0..MAX_PAGES {
+      let page: Page<MailingList> = self
+        .user_page(
+          "lists",
+          &format!("{}{}", query, PATCHSET_FIELDS),
+          serde_json::json!({ "username": username, "cursor": cursor }),
+          "/lists",
+        )
+        .await?;
+
+      for mut list in page.results {
+        for _ in 1..MAX_PAGES {
+          let Some(next) = list.patches.cursor.take() else {
+            break;
+          };
+          let more: Page<Patchset> = self
+            .user_page(
+              "lists",
+              &format!("{}{}", patches_query, PATCHSET_FIELDS),
+              serde_json::json!({ "username": username, "name": list.name, "cursor": next }),
+              "/list/patches",
+            )
+            .await?;
+          list.patches.results.extend(more.results);
+          list.patches.cursor = more.cursor;
+        }
+        lists.push(list);
+      }
+
+      cursor = page.cursor;
+      if cursor.is_none() {
+        break;
+      }
+    }
+
+    Ok(lists)
+  }
+
+  async fn fetch_patchsets(&self, username: &str) -> Result<Vec<WorkItem>> {
+    let base = self.service_url("lists");
+    let mut patchsets = Vec::new();
+
+    for list in self.fetch_lists(username).await? {
+      for patchset in list.patches.results {
+        if !OPEN_PATCHSET_STATUSES.contains(&patchset.status.as_str()) {
+          continue;
+        }
+
+        patchsets.push(WorkItem {
+          item: SearchItem {
+            title: patchset.subject,
+            repo: format!("~{}/{}", username, list.name),
+            url: format!(
+              "{}/~{}/{}/patches/{}",
+              base, username, list.name, patchset.id
+            ),
+            number: patchset.id,
+            origin: None,
+          },
+          submitter: patchset
+            .submitter
+            .map(|e| e.canonical_name)
+            .unwrap_or_default(),
+          assignees: Vec::new(),
+        });
+      }
     }
 
-    serde_json::to_value(language_percentages).unwrap_or_else(|_| serde_json::json!({}))
+    Ok(patchsets)
+  }
+}
+
+/// sr.ht canonical names carry a leading `~` that the API does not expect
+fn normalize_username(username: &str) -> &str {
+  username.trim_start_matches('~')
+}
+
+fn summarize<'a>(items: impl Iterator<Item = &'a WorkItem>, per_page: usize) -> SearchResult {
+  let items: Vec<&WorkItem> = items.collect();
+  SearchResult {
+    total_count: items.len() as u32,
+    items: items
+      .iter()
+      .take(per_page)
+      .map(|work| work.item.clone())
+      .collect(),
   }
 }
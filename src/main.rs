@@ -12,7 +12,10 @@ mod utils;
 use cache::CacheManager;
 use cli::{interactive, Cli};
 use config::ConfigManager;
-use display::DisplayFormatter;
+use display::{DisplayFormatter, OutputFormat};
+use models::{UserData, UserStats};
+use std::path::Path;
+use std::sync::Arc;
 
 async fn check_for_updates() -> Result<Option<String>> {
   let client = reqwest::Client::new();
@@ -74,6 +77,7 @@ async fn main() -> Result<()> {
 
   // Config initialization
   let mut config_manager = ConfigManager::new()?;
+  config_manager.use_profile(args.profile.as_deref(), args.change_provider)?;
 
   // Change provider
   if args.change_provider {
@@ -102,17 +106,15 @@ async fn main() -> Result<()> {
   // Clone config for later use (before any borrowing)
   let config_clone = config_manager.config.clone();
 
+  let range = utils::calendar::DateRange::from_args(args.year, args.from, args.to)?;
+
   // Visual options for display
   let visual_opts = display::VisualOptions {
     graph_only: args.graph_only,
-    spaced: if args.not_spaced {
-      false
-    } else if args.spaced {
-      true
-    } else {
-      true // Default to spaced mode
-    },
+    // Default to spaced mode; --spaced is kept for explicitness
+    spaced: !args.not_spaced,
     graph_timeline: args.graph_timeline,
+    language_bar: args.language_bar,
     width: args.width,
     height: args.height,
     no_achievements: args.no_achievements,
@@ -121,6 +123,7 @@ async fn main() -> Result<()> {
     no_pr: args.no_pr,
     no_account: args.no_account,
     no_grid: args.no_grid,
+    range,
   };
 
   // Handle text/shape simulation
@@ -140,19 +143,141 @@ async fn main() -> Result<()> {
     return Ok(());
   }
 
+  let local_options = utils::git::LocalOptions {
+    all_authors: args.all_authors,
+    aliases: config_clone.author_aliases.clone(),
+    range,
+  };
+
+  // Every repository under a directory, merged into one card
+  if let Some(root) = &args.local_scan {
+    let workspace = utils::git::analyze_workspace(root, &local_options)?;
+    let username = workspace.user_data.name.as_deref().unwrap_or("Local User");
+
+    render(
+      args.format,
+      args.output.as_deref(),
+      config_clone.clone(),
+      visual_opts.clone(),
+      username,
+      &workspace.user_data,
+      &workspace.stats,
+    )?;
+
+    if args.format == OutputFormat::Text && args.output.is_none() {
+      DisplayFormatter::new(config_clone, visual_opts)?.display_repo_commits(&workspace.repos)?;
+    }
+
+    return Ok(());
+  }
+
   // Handle local mode
   if args.local {
-    use std::path::Path;
-
     if !Path::new(".git").exists() {
       return Err(anyhow::anyhow!("Error: --local requires .git folder"));
     }
 
-    let local_data = utils::git::analyze_local_repo()?;
-    let username = local_data["name"].as_str().unwrap_or("Local User");
+    let (user_data, stats) = utils::git::analyze_local_repo(&local_options)?;
+    let username = user_data.name.as_deref().unwrap_or("Local User");
 
-    let formatter = DisplayFormatter::new(config_clone, visual_opts)?;
-    formatter.display(username, &local_data, &local_data)?;
+    render(
+      args.format,
+      args.output.as_deref(),
+      config_clone,
+      visual_opts,
+      username,
+      &user_data,
+      &stats,
+    )?;
+
+    return Ok(());
+  }
+
+  let fetch_options = fetcher::FetchOptions {
+    language_weighting: config_clone.language_weighting,
+    range,
+    include_private: args.include_private,
+  };
+  // Other date ranges, private calendars and repo-counted languages are
+  // cached apart from the default ones
+  let mut key_suffix = range
+    .map(|range| format!("@{}..{}", range.start, range.end))
+    .unwrap_or_default();
+  if args.include_private {
+    key_suffix.push_str("+private");
+  }
+  if fetch_options.language_weighting == config::LanguageWeighting::Repos {
+    key_suffix.push_str("+repos");
+  }
+
+  // Aggregate every configured account; also the only option when no
+  // single provider is set up
+  if args.all_accounts || (config_clone.provider.is_none() && !config_clone.accounts.is_empty()) {
+    let accounts = &config_clone.accounts;
+    if accounts.is_empty() {
+      return Err(anyhow::anyhow!(
+        "No accounts configured: add [accounts.<name>] sections to the config"
+      ));
+    }
+    // Without a provider, these single-account views have nothing to use
+    if args.repo.is_some() || args.org.is_some() || args.history {
+      return Err(anyhow::anyhow!(
+        "--repo, --org and --history need a configured provider; they can't combine [accounts]"
+      ));
+    }
+
+    let cache_manager = CacheManager::new(config_clone.cache_expiry_minutes)?;
+    let cache_key = format!(
+      "accounts:{}{}",
+      accounts.keys().cloned().collect::<Vec<_>>().join(","),
+      key_suffix
+    );
+
+    let cached = if args.no_cache {
+      None
+    } else {
+      match (
+        cache_manager.get_cached_user_data(&cache_key)?,
+        cache_manager.get_cached_stats(&cache_key)?,
+      ) {
+        (Some(user_data), Some(stats)) => Some((user_data, stats)),
+        _ => None,
+      }
+    };
+
+    let (user_data, stats) = match cached {
+      Some(cached) => cached,
+      None => {
+        let mut fetched = Vec::new();
+        for (name, result) in fetcher::accounts::fetch_accounts(accounts, &fetch_options).await {
+          match result {
+            Ok(data) => fetched.push(data),
+            Err(e) => eprintln!("Skipping account {}: {}", name, e),
+          }
+        }
+        if fetched.is_empty() {
+          return Err(anyhow::anyhow!("Could not fetch any configured account"));
+        }
+
+        let (user_data, stats) = fetcher::accounts::merge_accounts(&fetched);
+        // Only cache complete aggregates
+        if fetched.len() == accounts.len() {
+          cache_manager.cache_user_data(&cache_key, &user_data, &stats)?;
+        }
+        (user_data, stats)
+      }
+    };
+
+    let username = user_data.login.clone();
+    render(
+      args.format,
+      args.output.as_deref(),
+      config_clone,
+      visual_opts,
+      &username,
+      &user_data,
+      &stats,
+    )?;
 
     return Ok(());
   }
@@ -163,13 +288,113 @@ async fn main() -> Result<()> {
     .ok_or_else(|| anyhow::anyhow!("Provider not configured"))?;
   let provider_url = config_manager
     .get_provider_url()
-    .unwrap_or("https://api.github.com");
+    .unwrap_or(fetcher::github::DEFAULT_API_URL);
   let token = config_manager.get_token();
 
   // Get cache expiry from cloned config
   let cache_expiry = config_clone.cache_expiry_minutes;
 
-  let fetcher = fetcher::create_fetcher(provider, provider_url, token)?;
+  let fetcher: Arc<dyn fetcher::Fetcher> =
+    fetcher::create_fetcher(provider, provider_url, token, &fetch_options)?.into();
+
+  // Cache manager; profiles get their own entries since the same username
+  // can belong to different people on different providers
+  let cache_manager = CacheManager::new(cache_expiry)?;
+  let cache_key_for = |login: &str| match config_manager.get_profile() {
+    Some(profile) => format!("{}:{}{}", profile, login, key_suffix),
+    None => format!("{}{}", login, key_suffix),
+  };
+
+  // Repository card
+  if let Some(repo) = args.repo.as_deref() {
+    let repo_stats = fetcher.fetch_repo_stats(repo).await?;
+    return render_repo(
+      args.format,
+      args.output.as_deref(),
+      config_clone,
+      visual_opts,
+      &repo_stats,
+    );
+  }
+
+  // Team view: combined card plus a leaderboard of the members
+  if let Some(org) = args.org.as_deref() {
+    let members = fetcher.fetch_org_members(org).await?;
+    if members.is_empty() {
+      return Err(anyhow::anyhow!("No members found for {}", org));
+    }
+
+    let mut fetched = Vec::new();
+    let mut missing = Vec::new();
+    for login in members {
+      let cached = if args.no_cache {
+        None
+      } else {
+        let key = cache_key_for(&login);
+        cache_manager
+          .get_cached_user_data(&key)?
+          .zip(cache_manager.get_cached_stats(&key)?)
+      };
+      match cached {
+        Some((user_data, stats)) => fetched.push(fetcher::accounts::AccountData {
+          name: login,
+          user_data,
+          stats,
+        }),
+        None => missing.push(login),
+      }
+    }
+
+    if !missing.is_empty() {
+      eprintln!("Fetching {} members of {}...", missing.len(), org);
+    }
+    for (login, result) in fetcher::accounts::fetch_members(
+      Arc::clone(&fetcher),
+      missing,
+      fetcher::accounts::MEMBER_CONCURRENCY,
+    )
+    .await
+    {
+      match result {
+        Ok(data) => {
+          cache_manager.cache_user_data(&cache_key_for(&login), &data.user_data, &data.stats)?;
+          fetched.push(data);
+        }
+        Err(e) => eprintln!("Skipping {}: {}", login, e),
+      }
+    }
+    if fetched.is_empty() {
+      return Err(anyhow::anyhow!("Could not fetch any member of {}", org));
+    }
+
+    let (merged_user, stats) = fetcher::accounts::merge_accounts(&fetched);
+    let user_data = UserData {
+      login: org.to_string(),
+      name: Some(format!("{} ({} members)", org, fetched.len())),
+      public_repos: merged_user.public_repos,
+      ..Default::default()
+    };
+
+    render(
+      args.format,
+      args.output.as_deref(),
+      config_clone.clone(),
+      visual_opts.clone(),
+      org,
+      &user_data,
+      &stats,
+    )?;
+
+    if args.format == OutputFormat::Text && args.output.is_none() {
+      let members: Vec<(&str, &UserStats)> = fetched
+        .iter()
+        .map(|member| (member.name.as_str(), &member.stats))
+        .collect();
+      DisplayFormatter::new(config_clone, visual_opts)?.display_leaderboard(&members)?;
+    }
+
+    return Ok(());
+  }
 
   // Determine username
   let username = if let Some(u) = args.username {
@@ -180,8 +405,35 @@ async fn main() -> Result<()> {
     fetcher.get_authenticated_user().await?
   };
 
-  // Cache manager
-  let cache_manager = CacheManager::new(cache_expiry)?;
+  // Yearly calendars since the account was created
+  if args.history {
+    if args.format != OutputFormat::Text {
+      return Err(anyhow::anyhow!("--history only supports text output"));
+    }
+
+    let user_data = fetcher.fetch_user_data(&username).await?;
+    let years = utils::calendar::history_years(user_data.created_at.as_deref());
+    eprintln!(
+      "Fetching {} years of contributions...",
+      years.clone().count()
+    );
+
+    let mut calendars = Vec::new();
+    for year in years {
+      let range = utils::calendar::DateRange::year(year)?;
+      calendars.push(utils::calendar::YearCalendar {
+        year,
+        weeks: fetcher
+          .fetch_contribution_calendar(&user_data, range)
+          .await?,
+      });
+    }
+
+    let name = user_data.name.as_deref().unwrap_or(&username);
+    return DisplayFormatter::new(config_clone, visual_opts)?.display_history(name, &calendars);
+  }
+
+  let cache_key = cache_key_for(&username);
 
   // Fetch data
   let (user_data, stats) = if args.no_cache {
@@ -191,35 +443,45 @@ async fn main() -> Result<()> {
       .await?;
     (user_data, stats)
   } else {
-    match cache_manager.get_cached_user_data(&username)? {
+    match cache_manager.get_cached_user_data(&cache_key)? {
       Some(cached_user) => {
         let cached_stats = cache_manager
-          .get_cached_stats(&username)?
+          .get_cached_stats(&cache_key)?
           .ok_or_else(|| anyhow::anyhow!("Cached stats not found"))?;
         (cached_user, cached_stats)
       }
       None => {
         // Try stale cache for immediate display
         match (
-          cache_manager.get_stale_cached_user_data(&username)?,
-          cache_manager.get_stale_cached_stats(&username)?,
+          cache_manager.get_stale_cached_user_data(&cache_key)?,
+          cache_manager.get_stale_cached_stats(&cache_key)?,
         ) {
           (Some(stale_user), Some(stale_stats)) => {
             // Display stale data immediately
-            let formatter = DisplayFormatter::new(config_clone.clone(), visual_opts)?;
-            formatter.display(&username, &stale_user, &stale_stats)?;
+            render(
+              args.format,
+              args.output.as_deref(),
+              config_clone.clone(),
+              visual_opts,
+              &username,
+              &stale_user,
+              &stale_stats,
+            )?;
 
             // Spawn background refresh
             let username_clone = username.clone();
+            let cache_key_clone = cache_key.clone();
             let provider_clone = provider.to_string();
             let provider_url_clone = provider_url.to_string();
             let token_clone = token.map(|s| s.to_string());
+            let options_clone = fetch_options.clone();
 
             tokio::spawn(async move {
               if let Ok(fetcher) = fetcher::create_fetcher(
                 &provider_clone,
                 &provider_url_clone,
                 token_clone.as_deref(),
+                &options_clone,
               ) {
                 if let Ok(user_data) = fetcher.fetch_user_data(&username_clone).await {
                   if let Ok(stats) = fetcher
@@ -227,7 +489,7 @@ async fn main() -> Result<()> {
                     .await
                   {
                     if let Ok(cache) = CacheManager::new(cache_expiry) {
-                      let _ = cache.cache_user_data(&username_clone, &user_data, &stats);
+                      let _ = cache.cache_user_data(&cache_key_clone, &user_data, &stats);
                     }
                   }
                 }
@@ -242,7 +504,7 @@ async fn main() -> Result<()> {
             let stats = fetcher
               .fetch_user_stats(&username, Some(&user_data))
               .await?;
-            cache_manager.cache_user_data(&username, &user_data, &stats)?;
+            cache_manager.cache_user_data(&cache_key, &user_data, &stats)?;
             (user_data, stats)
           }
         }
@@ -251,8 +513,113 @@ async fn main() -> Result<()> {
   };
 
   // Display
-  let formatter = DisplayFormatter::new(config_clone, visual_opts)?;
-  formatter.display(&username, &user_data, &stats)?;
+  render(
+    args.format,
+    args.output.as_deref(),
+    config_clone,
+    visual_opts,
+    &username,
+    &user_data,
+    &stats,
+  )
+}
+
+fn render(
+  format: OutputFormat,
+  output: Option<&Path>,
+  config: config::Config,
+  visual_opts: display::VisualOptions,
+  username: &str,
+  user_data: &UserData,
+  stats: &UserStats,
+) -> Result<()> {
+  let extension = output
+    .and_then(|path| path.extension())
+    .and_then(|ext| ext.to_str())
+    .map(|ext| ext.to_ascii_lowercase());
+
+  let rendered = match (extension.as_deref(), format) {
+    (Some("svg"), _) => {
+      let formatter = DisplayFormatter::new(config, visual_opts)?;
+      formatter
+        .render_svg(username, user_data, stats)
+        .into_bytes()
+    }
+    (Some("png"), _) => {
+      let formatter = DisplayFormatter::new(config, visual_opts)?;
+      formatter.render_png(username, user_data, stats)?
+    }
+    (Some("html" | "htm"), _) | (_, OutputFormat::Html) => {
+      let formatter = DisplayFormatter::new(config, visual_opts)?;
+      formatter
+        .render_html(username, user_data, stats)
+        .into_bytes()
+    }
+    (_, OutputFormat::Text) => {
+      if let Some(path) = output {
+        return Err(anyhow::anyhow!(
+          "Unsupported output file {}: use .svg, .png or .html, or pass --format",
+          path.display()
+        ));
+      }
+      let formatter = DisplayFormatter::new(config, visual_opts)?;
+      return formatter.display(username, user_data, stats);
+    }
+    (_, format) => display::export::ExportDocument::new(username, user_data, stats)
+      .render(format)?
+      .into_bytes(),
+  };
+
+  write_output(output, rendered)
+}
+
+fn render_repo(
+  format: OutputFormat,
+  output: Option<&Path>,
+  config: config::Config,
+  visual_opts: display::VisualOptions,
+  repo: &models::RepoStats,
+) -> Result<()> {
+  let extension = output
+    .and_then(|path| path.extension())
+    .and_then(|ext| ext.to_str())
+    .map(|ext| ext.to_ascii_lowercase());
+
+  let rendered = match (extension.as_deref(), format) {
+    (Some("png"), _) => DisplayFormatter::new(config, visual_opts)?.render_repo_png(repo)?,
+    (Some("svg" | "html" | "htm"), _) | (_, OutputFormat::Html) => {
+      return Err(anyhow::anyhow!(
+        "Repository cards support the terminal, .png and --format json|yaml|toml"
+      ));
+    }
+    (_, OutputFormat::Text) => {
+      if let Some(path) = output {
+        return Err(anyhow::anyhow!(
+          "Unsupported output file {}: use .png, or pass --format",
+          path.display()
+        ));
+      }
+      return DisplayFormatter::new(config, visual_opts)?.display_repo(repo);
+    }
+    (_, format) => display::export::RepoExportDocument::new(repo)
+      .render(format)?
+      .into_bytes(),
+  };
+
+  write_output(output, rendered)
+}
+
+fn write_output(output: Option<&Path>, rendered: Vec<u8>) -> Result<()> {
+  match output {
+    Some(path) => {
+      std::fs::write(path, rendered)?;
+      eprintln!("Saved {}", path.display());
+    }
+    None => {
+      use std::io::Write;
+      std::io::stdout().write_all(&rendered)?;
+    }
+  }
 
   Ok(())
 }
@@ -263,13 +630,18 @@ async fn initialize_gitfetch(config_manager: &mut ConfigManager) -> Result<()> {
 
   match provider.as_str() {
     "github" => {
-      config_manager.set_provider_url("https://api.github.com".to_string());
+      let url = interactive::prompt_url(&provider, Some("https://github.com"))?;
+      config_manager.set_provider_url(url);
+      let token = interactive::prompt_token(&provider)?;
+      if !token.is_empty() {
+        config_manager.set_token(token);
+      }
     }
     "gitlab" => {
       config_manager.set_provider_url("https://gitlab.com".to_string());
     }
     "gitea" => {
-      let url = interactive::prompt_url(&provider)?;
+      let url = interactive::prompt_url(&provider, None)?;
       config_manager.set_provider_url(url);
       let token = interactive::prompt_token(&provider)?;
       if !token.is_empty() {
@@ -283,6 +655,14 @@ async fn initialize_gitfetch(config_manager: &mut ConfigManager) -> Result<()> {
         config_manager.set_token(token);
       }
     }
+    "bitbucket" => {
+      let url = interactive::prompt_url(&provider, Some("https://bitbucket.org"))?;
+      config_manager.set_provider_url(url);
+      let token = interactive::prompt_token(&provider)?;
+      if !token.is_empty() {
+        config_manager.set_token(token);
+      }
+    }
     _ => {}
   }
 
@@ -290,6 +670,7 @@ async fn initialize_gitfetch(config_manager: &mut ConfigManager) -> Result<()> {
     &provider,
     config_manager.get_provider_url().unwrap_or(""),
     config_manager.get_token(),
+    &fetcher::FetchOptions::default(),
   )?;
 
   match fetcher.get_authenticated_user().await {
@@ -300,7 +681,7 @@ async fn initialize_gitfetch(config_manager: &mut ConfigManager) -> Result<()> {
     Err(e) => {
       eprintln!("Could not get authenticated user: {}", e);
       if provider == "github" {
-        eprintln!("Please authenticate with: gh auth login");
+        eprintln!("Please set GITHUB_TOKEN or authenticate with: gh auth login");
       } else if provider == "gitlab" {
         eprintln!("Please authenticate with: glab auth login");
       } else {
@@ -1,2 +1,10 @@
+pub mod repo;
 pub mod stats;
 pub mod user;
+
+pub use repo::{Contributor, RepoStats};
+pub use stats::{
+  ContributionDay, ContributionWeek, IssueStats, LineChanges, PullRequestStats, SearchItem,
+  SearchResult, UserStats,
+};
+pub use user::UserData;
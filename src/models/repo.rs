@@ -0,0 +1,29 @@
+use super::stats::{ContributionWeek, SearchResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Everything shown on a `--repo` card
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct RepoStats {
+  /// `owner/name` as reported by the provider
+  pub full_name: String,
+  pub description: Option<String>,
+  pub url: Option<String>,
+  pub stars: u32,
+  pub forks: u32,
+  /// Share of the code per language, in percent
+  pub languages: HashMap<String, f64>,
+  /// Commits per day over the last year
+  pub commit_activity: Vec<ContributionWeek>,
+  /// Most active committers, busiest first
+  pub contributors: Vec<Contributor>,
+  pub pull_requests: SearchResult,
+  pub issues: SearchResult,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Contributor {
+  pub login: String,
+  pub contributions: u32,
+}
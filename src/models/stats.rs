@@ -1,33 +1,145 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
-#[allow(dead_code)]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct UserStats {
+  #[serde(deserialize_with = "null_as_default")]
   pub total_stars: u32,
+  #[serde(deserialize_with = "null_as_default")]
   pub total_forks: u32,
+  #[serde(deserialize_with = "null_as_default")]
   pub total_repos: u32,
+  #[serde(deserialize_with = "null_as_default")]
   pub languages: HashMap<String, f64>,
-  pub contribution_graph: serde_json::Value,
+  /// Amounts `languages` was computed from: bytes, or repos when counted by
+  /// primary language. Accounts are merged on these.
+  #[serde(
+    deserialize_with = "null_as_default",
+    skip_serializing_if = "HashMap::is_empty"
+  )]
+  pub language_totals: HashMap<String, f64>,
+  #[serde(deserialize_with = "null_as_default")]
+  pub contribution_graph: Vec<ContributionWeek>,
+  #[serde(deserialize_with = "null_as_default")]
   pub current_streak: u32,
+  #[serde(deserialize_with = "null_as_default")]
   pub longest_streak: u32,
+  #[serde(deserialize_with = "null_as_default")]
   pub total_contributions: u32,
   pub pull_requests: PullRequestStats,
   pub issues: IssueStats,
+  /// Per-day diff totals keyed by `YYYY-MM-DD`; only local analysis has them
+  #[serde(
+    deserialize_with = "null_as_default",
+    skip_serializing_if = "BTreeMap::is_empty"
+  )]
+  pub line_changes: BTreeMap<String, LineChanges>,
+  /// Set when the calendar includes the token owner's private activity:
+  /// how many of its contributions are restricted (private) ones
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub private_contributions: Option<u32>,
+}
+
+/// Files and lines touched by commits
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct LineChanges {
+  pub files_changed: u32,
+  pub additions: u32,
+  pub deletions: u32,
+}
+
+impl LineChanges {
+  pub fn add(&mut self, other: &LineChanges) {
+    self.files_changed += other.files_changed;
+    self.additions += other.additions;
+    self.deletions += other.deletions;
+  }
+}
+
+/// One column of the contribution calendar, using GitHub's GraphQL field names
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ContributionWeek {
+  #[serde(rename = "contributionDays", default)]
+  pub contribution_days: Vec<ContributionDay>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ContributionDay {
+  #[serde(rename = "contributionCount", default)]
+  pub contribution_count: u32,
+  #[serde(default)]
+  pub date: String,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct PullRequestStats {
-  pub awaiting_review: u32,
-  pub open: u32,
-  pub mentions: u32,
+  pub awaiting_review: SearchResult,
+  pub open: SearchResult,
+  pub mentions: SearchResult,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct IssueStats {
-  pub assigned: u32,
-  pub created: u32,
-  pub mentions: u32,
+  pub assigned: SearchResult,
+  pub created: SearchResult,
+  pub mentions: SearchResult,
+}
+
+/// A count plus the first few matching pull requests or issues
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(from = "SearchResultRepr")]
+pub struct SearchResult {
+  pub total_count: u32,
+  pub items: Vec<SearchItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct SearchItem {
+  pub title: String,
+  pub repo: String,
+  pub url: String,
+  pub number: i64,
+  /// Account the item came from when several accounts are aggregated
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub origin: Option<String>,
+}
+
+/// Cache rows written before the typed model stored some counters as bare
+/// numbers (`"open": 0`) instead of `{total_count, items}` objects.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SearchResultRepr {
+  Count(u32),
+  Full {
+    #[serde(default)]
+    total_count: u32,
+    #[serde(default)]
+    items: Vec<SearchItem>,
+  },
+}
+
+impl From<SearchResultRepr> for SearchResult {
+  fn from(repr: SearchResultRepr) -> Self {
+    match repr {
+      SearchResultRepr::Count(total_count) => Self {
+        total_count,
+        items: Vec::new(),
+      },
+      SearchResultRepr::Full { total_count, items } => Self { total_count, items },
+    }
+  }
+}
+
+/// Treat an explicit `null` like a missing field
+pub(crate) fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+  D: Deserializer<'de>,
+  T: Default + Deserialize<'de>,
+{
+  Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
 }
@@ -1,17 +1,28 @@
+use super::stats::null_as_default;
 use serde::{Deserialize, Serialize};
 
-#[allow(dead_code)]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Profile information shown next to the contribution graph.
+///
+/// Field names follow GitHub's REST API so that cache rows holding raw
+/// GitHub user payloads still deserialize.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct UserData {
+  #[serde(alias = "username", deserialize_with = "null_as_default")]
   pub login: String,
+  pub id: Option<u64>,
   pub name: Option<String>,
   pub bio: Option<String>,
   pub company: Option<String>,
+  #[serde(alias = "website")]
   pub blog: Option<String>,
   pub location: Option<String>,
   pub email: Option<String>,
+  #[serde(deserialize_with = "null_as_default")]
   pub public_repos: u32,
+  #[serde(deserialize_with = "null_as_default")]
   pub followers: u32,
+  #[serde(deserialize_with = "null_as_default")]
   pub following: u32,
   pub created_at: Option<String>,
 }
@@ -0,0 +1,272 @@
+use crate::models::{ContributionDay, ContributionWeek};
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use std::collections::HashMap;
+
+/// Days in the default rolling window, today included
+pub const DEFAULT_RANGE_DAYS: i64 = 365;
+
+/// Default calendar window: the rolling year ending today, like GitHub's
+/// profile calendar.
+pub fn default_range() -> (NaiveDate, NaiveDate) {
+  let today = Utc::now().date_naive();
+  (today - Duration::days(DEFAULT_RANGE_DAYS - 1), today)
+}
+
+/// Days a contribution calendar covers, both ends included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+  pub start: NaiveDate,
+  pub end: NaiveDate,
+}
+
+impl Default for DateRange {
+  fn default() -> Self {
+    let (start, end) = default_range();
+    DateRange { start, end }
+  }
+}
+
+impl DateRange {
+  pub fn new(start: NaiveDate, end: NaiveDate) -> Result<Self> {
+    if start > end {
+      return Err(anyhow::anyhow!(
+        "Range start {} is after its end {}",
+        start,
+        end
+      ));
+    }
+    Ok(DateRange { start, end })
+  }
+
+  /// A calendar year, ending today while it is still running
+  pub fn year(year: i32) -> Result<Self> {
+    let start = NaiveDate::from_ymd_opt(year, 1, 1)
+      .ok_or_else(|| anyhow::anyhow!("Invalid year: {}", year))?;
+    let end = NaiveDate::from_ymd_opt(year, 12, 31)
+      .ok_or_else(|| anyhow::anyhow!("Invalid year: {}", year))?;
+    let today = Utc::now().date_naive();
+    if start > today {
+      return Err(anyhow::anyhow!("{} hasn't started yet", year));
+    }
+    Ok(DateRange {
+      start,
+      end: end.min(today),
+    })
+  }
+
+  /// Range for `--year` or `--from`/`--to`; `None` keeps the default
+  /// rolling year. A missing `to` means today, a missing `from` the
+  /// [`DEFAULT_RANGE_DAYS`] days ending at `to`.
+  pub fn from_args(
+    year: Option<i32>,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+  ) -> Result<Option<Self>> {
+    match (year, from, to) {
+      (Some(year), _, _) => DateRange::year(year).map(Some),
+      (None, None, None) => Ok(None),
+      (None, from, to) => {
+        let end = to.unwrap_or_else(|| Utc::now().date_naive());
+        let start = from.unwrap_or(end - Duration::days(DEFAULT_RANGE_DAYS - 1));
+        DateRange::new(start, end).map(Some)
+      }
+    }
+  }
+
+  pub fn contains(&self, date: NaiveDate) -> bool {
+    self.start <= date && date <= self.end
+  }
+
+  /// Consecutive pieces of at most `days` days covering the range
+  pub fn chunks(&self, days: i64) -> Vec<DateRange> {
+    let mut chunks = Vec::new();
+    let mut start = self.start;
+    while start <= self.end {
+      let end = (start + Duration::days(days - 1)).min(self.end);
+      chunks.push(DateRange { start, end });
+      start = end + Duration::days(1);
+    }
+    chunks
+  }
+
+  /// Calendar year the range spans, when it starts on January 1st and runs
+  /// to December 31st or to today
+  pub fn calendar_year(&self) -> Option<i32> {
+    let year = self.start.year();
+    let whole_year = self.start.ordinal() == 1
+      && self.end.year() == year
+      && (self.end.succ_opt().is_some_and(|next| next.year() != year)
+        || self.end == Utc::now().date_naive());
+    whole_year.then_some(year)
+  }
+
+  /// Wording for headers: "in 2024" or "from 2024-03-01 to 2024-06-30"
+  pub fn describe(&self) -> String {
+    match self.calendar_year() {
+      Some(year) => format!("in {}", year),
+      None => format!("from {} to {}", self.start, self.end),
+    }
+  }
+}
+
+/// Build contribution calendar weeks from per-day counts.
+///
+/// Weeks start on Sunday, so the first week is padded back to the Sunday on
+/// or before `start` with empty days. The last week stops at `end`.
+pub fn weeks_from_daily_counts(
+  counts: &HashMap<NaiveDate, u32>,
+  start: NaiveDate,
+  end: NaiveDate,
+) -> Vec<ContributionWeek> {
+  let mut current_date = start - Duration::days(start.weekday().num_days_from_sunday() as i64);
+
+  let mut weeks = Vec::new();
+  while current_date <= end {
+    let mut week_days = Vec::new();
+
+    for _ in 0..7 {
+      if current_date > end {
+        break;
+      }
+
+      let count = if current_date < start {
+        0
+      } else {
+        counts.get(&current_date).copied().unwrap_or(0)
+      };
+      week_days.push(ContributionDay {
+        contribution_count: count,
+        date: current_date.format("%Y-%m-%d").to_string(),
+      });
+
+      current_date += Duration::days(1);
+    }
+
+    weeks.push(ContributionWeek {
+      contribution_days: week_days,
+    });
+  }
+
+  weeks
+}
+
+/// Per-day counts of a calendar, skipping days with unparsable dates
+pub fn daily_counts(weeks: &[ContributionWeek]) -> HashMap<NaiveDate, u32> {
+  let mut counts = HashMap::new();
+  for day in weeks.iter().flat_map(|w| &w.contribution_days) {
+    if let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") {
+      *counts.entry(date).or_insert(0) += day.contribution_count;
+    }
+  }
+  counts
+}
+
+/// Sums several contribution calendars day by day.
+///
+/// The result spans from the earliest to the latest date found in any of
+/// them; days missing from one calendar count as zero.
+pub fn merge_calendars<'a, I>(calendars: I) -> Vec<ContributionWeek>
+where
+  I: IntoIterator<Item = &'a [ContributionWeek]>,
+{
+  let mut counts: HashMap<NaiveDate, u32> = HashMap::new();
+
+  for weeks in calendars {
+    for (date, count) in daily_counts(weeks) {
+      *counts.entry(date).or_insert(0) += count;
+    }
+  }
+
+  match (counts.keys().min(), counts.keys().max()) {
+    (Some(&start), Some(&end)) => weeks_from_daily_counts(&counts, start, end),
+    _ => Vec::new(),
+  }
+}
+
+/// One calendar year of a `--history` view
+#[derive(Debug, Clone)]
+pub struct YearCalendar {
+  pub year: i32,
+  pub weeks: Vec<ContributionWeek>,
+}
+
+impl YearCalendar {
+  /// Daily counts within the year, leaving out the days the first week is
+  /// padded with
+  fn days(&self) -> impl Iterator<Item = (NaiveDate, u32)> + '_ {
+    self
+      .weeks
+      .iter()
+      .flat_map(|w| &w.contribution_days)
+      .filter_map(|day| {
+        let date = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d").ok()?;
+        (date.year() == self.year).then_some((date, day.contribution_count))
+      })
+  }
+
+  pub fn total(&self) -> u32 {
+    self.days().map(|(_, count)| count).sum()
+  }
+
+  /// Longest run of active days within the year
+  pub fn longest_streak(&self) -> u32 {
+    let mut longest = 0;
+    let mut current = 0;
+    for (_, count) in self.days() {
+      current = if count > 0 { current + 1 } else { 0 };
+      longest = longest.max(current);
+    }
+    longest
+  }
+}
+
+/// All-time figures across the years of a `--history` view
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistorySummary {
+  pub total: u32,
+  /// Busiest year and its total; the earliest one wins a tie
+  pub best_year: Option<(i32, u32)>,
+  /// First day of the busiest month and its total
+  pub best_month: Option<(NaiveDate, u32)>,
+}
+
+pub fn history_summary(years: &[YearCalendar]) -> HistorySummary {
+  let mut summary = HistorySummary::default();
+  let mut months: HashMap<NaiveDate, u32> = HashMap::new();
+
+  for calendar in years {
+    let total = calendar.total();
+    summary.total += total;
+    if total > summary.best_year.map_or(0, |(_, best)| best) {
+      summary.best_year = Some((calendar.year, total));
+    }
+
+    for (date, count) in calendar.days() {
+      if let Some(month) = date.with_day(1) {
+        *months.entry(month).or_insert(0) += count;
+      }
+    }
+  }
+
+  let mut months: Vec<(NaiveDate, u32)> = months.into_iter().filter(|(_, c)| *c > 0).collect();
+  months.sort();
+  for (month, count) in months {
+    if count > summary.best_month.map_or(0, |(_, best)| best) {
+      summary.best_month = Some((month, count));
+    }
+  }
+
+  summary
+}
+
+/// Years from an account's `created_at` through the current one; just the
+/// current year when the creation date is unknown
+pub fn history_years(created_at: Option<&str>) -> std::ops::RangeInclusive<i32> {
+  let current = Utc::now().year();
+  let first = created_at
+    .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+    .map(|date| date.year())
+    .unwrap_or(current);
+  first.min(current)..=current
+}
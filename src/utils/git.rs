@@ -1,8 +1,94 @@
+use super::calendar::{self, DateRange};
+use super::languages;
+use crate::display::graph::ContributionGraph;
+use crate::models::{LineChanges, UserData, UserStats};
 use anyhow::Result;
-use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
-use git2::Repository;
-use serde_json::{Value, json};
-use std::collections::HashMap;
+use chrono::{DateTime, NaiveDate};
+use git2::{Mailmap, ObjectType, Repository, Signature, TreeWalkMode, TreeWalkResult};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Directories left out of the language breakdown, like linguist's vendored
+/// paths
+const VENDORED_DIRS: &[&str] = &["node_modules", "vendor", "third_party"];
+
+#[derive(Debug, Clone, Default)]
+pub struct LocalOptions {
+  /// Count every commit instead of only those authored by `user.email`
+  pub all_authors: bool,
+  /// Other identities that count as the user: emails, or author names for
+  /// entries without an `@`
+  pub aliases: Vec<String>,
+  /// Calendar window; `None` is the rolling year ending today
+  pub range: Option<DateRange>,
+}
+
+/// Identities whose commits count, compared after `.mailmap` is applied
+#[derive(Debug, Default)]
+struct AuthorFilter {
+  emails: Vec<String>,
+  names: Vec<String>,
+}
+
+impl AuthorFilter {
+  fn new(mailmap: &Mailmap, user_name: &str, user_email: &str, aliases: &[String]) -> Self {
+    let mut filter = AuthorFilter::default();
+    if !user_email.is_empty() {
+      filter.emails.push(user_email.to_lowercase());
+      // The configured address may itself be mapped to a canonical one
+      if let Ok(signature) = Signature::now(user_name, user_email) {
+        if let Some(email) = mailmap
+          .resolve_signature(&signature)
+          .ok()
+          .and_then(|s| s.email().map(str::to_lowercase))
+        {
+          filter.emails.push(email);
+        }
+      }
+    }
+
+    for alias in aliases {
+      if alias.contains('@') {
+        filter.emails.push(alias.to_lowercase());
+      } else {
+        filter.names.push(alias.to_lowercase());
+      }
+    }
+
+    filter
+  }
+
+  fn is_empty(&self) -> bool {
+    self.emails.is_empty() && self.names.is_empty()
+  }
+
+  fn matches(&self, signatures: &[&Signature]) -> bool {
+    signatures.iter().any(|signature| {
+      let email = signature.email().unwrap_or_default().to_lowercase();
+      let name = signature.name().unwrap_or_default().to_lowercase();
+      self.emails.contains(&email) || self.names.contains(&name)
+    })
+  }
+}
+
+/// Directories `find_repositories` doesn't descend into
+const SKIPPED_SCAN_DIRS: &[&str] = &["node_modules", "target"];
+
+/// Merged result of `analyze_workspace`
+#[derive(Debug, Clone)]
+pub struct Workspace {
+  pub user_data: UserData,
+  pub stats: UserStats,
+  /// Commit count per repository, busiest first
+  pub repos: Vec<(String, u32)>,
+}
+
+/// Commits and diff totals per day
+#[derive(Debug, Default)]
+struct Activity {
+  commits: HashMap<NaiveDate, u32>,
+  changes: BTreeMap<String, LineChanges>,
+}
 
 pub fn get_repo_path() -> Result<String> {
   let repo = Repository::discover(".")?;
@@ -13,8 +99,15 @@ pub fn get_repo_path() -> Result<String> {
   Ok(path.to_string_lossy().to_string())
 }
 
-pub fn analyze_local_repo() -> Result<Value> {
-  let repo = Repository::discover(".")?;
+pub fn analyze_local_repo(options: &LocalOptions) -> Result<(UserData, UserStats)> {
+  analyze_repo(Path::new(&get_repo_path()?), options)
+}
+
+/// Builds a card for the repository containing `path` from its history:
+/// the contribution calendar and streaks, per-day diff totals, and
+/// languages from the files tracked at HEAD.
+pub fn analyze_repo(path: &Path, options: &LocalOptions) -> Result<(UserData, UserStats)> {
+  let repo = Repository::discover(path)?;
 
   // Get current user from git config
   let config = repo.config()?;
@@ -25,89 +118,247 @@ pub fn analyze_local_repo() -> Result<Value> {
     .get_string("user.email")
     .unwrap_or_else(|_| "".to_string());
 
-  // Collect commits from last 52 weeks
-  let mut revwalk = repo.revwalk()?;
-  revwalk.push_head()?;
+  let mailmap = repo.mailmap()?;
+  let filter = AuthorFilter::new(&mailmap, &user_name, &user_email, &options.aliases);
+  let author = if options.all_authors {
+    None
+  } else if filter.is_empty() {
+    eprintln!("Warning: user.email is not set; counting commits by all authors");
+    None
+  } else {
+    Some(&filter)
+  };
+
+  let range = options.range.unwrap_or_default();
+  let activity = collect_activity(&repo, &mailmap, author, range)?;
+  let contribution_graph =
+    calendar::weeks_from_daily_counts(&activity.commits, range.start, range.end);
+  let graph = ContributionGraph::from_weeks(&contribution_graph);
+  let (current_streak, longest_streak) = graph.calculate_streaks();
+  let total_contributions = graph.calculate_total_contributions();
 
-  let now = Utc::now();
-  let one_year_ago = now - Duration::weeks(52);
+  let repo_path = repo.workdir().unwrap_or_else(|| repo.path());
 
-  let mut commits_by_date: HashMap<NaiveDate, u32> = HashMap::new();
-  let mut total_commits = 0;
+  let user_data = UserData {
+    login: user_name.clone(),
+    name: Some(user_name),
+    email: Some(user_email),
+    bio: Some(format!("Local repository: {}", repo_path.display())),
+    ..Default::default()
+  };
+
+  let stats = UserStats {
+    total_repos: 1,
+    languages: tree_languages(&repo)?,
+    contribution_graph,
+    current_streak,
+    longest_streak,
+    total_contributions,
+    line_changes: activity.changes,
+    ..Default::default()
+  };
+
+  Ok((user_data, stats))
+}
 
+/// Walks HEAD's history, keeping commits authored within `range` (by
+/// `author` when given, as either the raw or the mailmapped signature)
+fn collect_activity(
+  repo: &Repository,
+  mailmap: &Mailmap,
+  author: Option<&AuthorFilter>,
+  range: DateRange,
+) -> Result<Activity> {
+  let mut revwalk = repo.revwalk()?;
+  revwalk.push_head()?;
+
+  let mut activity = Activity::default();
   for oid in revwalk {
-    let oid = oid?;
-    let commit = repo.find_commit(oid)?;
+    let commit = repo.find_commit(oid?)?;
+    let signature = commit.author();
 
-    let commit_time = commit.time();
-    let timestamp = commit_time.seconds();
-    let datetime =
-      DateTime::from_timestamp(timestamp, 0).ok_or_else(|| anyhow::anyhow!("Invalid timestamp"))?;
+    if let Some(author) = author {
+      let mapped = commit.author_with_mailmap(mailmap)?;
+      if !author.matches(&[&signature, &mapped]) {
+        continue;
+      }
+    }
 
-    // Only count commits from last 52 weeks
-    if datetime < one_year_ago {
+    let date = DateTime::from_timestamp(signature.when().seconds(), 0)
+      .ok_or_else(|| anyhow::anyhow!("Invalid timestamp"))?
+      .date_naive();
+    if !range.contains(date) {
       continue;
     }
 
-    let date = datetime.date_naive();
-    *commits_by_date.entry(date).or_insert(0) += 1;
-    total_commits += 1;
+    *activity.commits.entry(date).or_insert(0) += 1;
+
+    // Merges only repeat changes already counted on their branches
+    if commit.parent_count() <= 1 {
+      let changes = commit_changes(repo, &commit)?;
+      activity
+        .changes
+        .entry(date.format("%Y-%m-%d").to_string())
+        .or_default()
+        .add(&changes);
+    }
   }
 
-  // Generate weeks data (52 weeks, 7 days each)
-  let mut weeks = Vec::new();
-  let mut current_date = one_year_ago.date_naive();
+  Ok(activity)
+}
+
+fn commit_changes(repo: &Repository, commit: &git2::Commit) -> Result<LineChanges> {
+  let tree = commit.tree()?;
+  let parent_tree = match commit.parents().next() {
+    Some(parent) => Some(parent.tree()?),
+    None => None,
+  };
 
-  // Start from Sunday
-  while current_date.weekday().num_days_from_sunday() != 0 {
-    current_date = current_date
-      .pred_opt()
-      .ok_or_else(|| anyhow::anyhow!("Date calculation error"))?;
+  let stats = repo
+    .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?
+    .stats()?;
+
+  Ok(LineChanges {
+    files_changed: stats.files_changed() as u32,
+    additions: stats.insertions() as u32,
+    deletions: stats.deletions() as u32,
+  })
+}
+
+/// Language percentages by the size of the files tracked at HEAD
+fn tree_languages(repo: &Repository) -> Result<HashMap<String, f64>> {
+  let tree = repo.head()?.peel_to_tree()?;
+  let odb = repo.odb()?;
+  let mut bytes: HashMap<String, f64> = HashMap::new();
+
+  tree.walk(TreeWalkMode::PreOrder, |_, entry| {
+    let name = entry.name().unwrap_or_default();
+    match entry.kind() {
+      Some(ObjectType::Tree) if VENDORED_DIRS.contains(&name) => TreeWalkResult::Skip,
+      Some(ObjectType::Blob) => {
+        if let Some(language) = languages::language_for_path(name) {
+          if let Ok((size, _)) = odb.read_header(entry.id()) {
+            *bytes.entry(language.to_string()).or_insert(0.0) += size as f64;
+          }
+        }
+        TreeWalkResult::Ok
+      }
+      _ => TreeWalkResult::Ok,
+    }
+  })?;
+
+  Ok(languages::percentages(bytes))
+}
+
+/// Work trees at or below `root`. Repositories aren't searched for nested
+/// ones, and hidden directories and symlinks are skipped.
+pub fn find_repositories(root: &Path) -> Result<Vec<PathBuf>> {
+  let mut repos = Vec::new();
+  let mut pending = vec![root.to_path_buf()];
+
+  while let Some(dir) = pending.pop() {
+    if dir.join(".git").exists() {
+      repos.push(dir);
+      continue;
+    }
+
+    let entries = match std::fs::read_dir(&dir) {
+      Ok(entries) => entries,
+      Err(e) if dir == root => return Err(e.into()),
+      Err(_) => continue,
+    };
+    for entry in entries.flatten() {
+      let name = entry.file_name();
+      let name = name.to_string_lossy();
+      let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+      if is_dir && !name.starts_with('.') && !SKIPPED_SCAN_DIRS.contains(&name.as_ref()) {
+        pending.push(entry.path());
+      }
+    }
   }
 
-  for _ in 0..52 {
-    let mut week_days = Vec::new();
+  repos.sort();
+  Ok(repos)
+}
+
+/// Analyzes every repository under `root` and merges them into one card.
+///
+/// Calendars and line changes are summed, streaks recomputed, and languages
+/// weighted by how many commits each repository contributed.
+pub fn analyze_workspace(root: &Path, options: &LocalOptions) -> Result<Workspace> {
+  let mut analyzed = Vec::new();
+  for path in find_repositories(root)? {
+    match analyze_repo(&path, options) {
+      Ok((user_data, stats)) => analyzed.push((path, user_data, stats)),
+      Err(e) => eprintln!("Skipping {}: {}", path.display(), e),
+    }
+  }
+  if analyzed.is_empty() {
+    return Err(anyhow::anyhow!(
+      "No git repositories with commits found under {}",
+      root.display()
+    ));
+  }
 
-    for _ in 0..7 {
-      let count = commits_by_date.get(&current_date).copied().unwrap_or(0);
-      week_days.push(json!({
-        "contributionCount": count,
-        "date": current_date.format("%Y-%m-%d").to_string(),
-      }));
+  let mut stats = UserStats {
+    total_repos: analyzed.len() as u32,
+    contribution_graph: calendar::merge_calendars(
+      analyzed
+        .iter()
+        .map(|(_, _, stats)| stats.contribution_graph.as_slice()),
+    ),
+    ..Default::default()
+  };
 
-      current_date = current_date
-        .succ_opt()
-        .ok_or_else(|| anyhow::anyhow!("Date calculation error"))?;
+  let mut languages: HashMap<String, f64> = HashMap::new();
+  let mut repo_commits = Vec::new();
+  for (path, _, repo_stats) in &analyzed {
+    let commits = repo_stats.total_contributions;
+    if commits > 0 {
+      for (lang, pct) in &repo_stats.languages {
+        *languages.entry(lang.clone()).or_insert(0.0) += pct * commits as f64;
+      }
+    }
+    for (date, changes) in &repo_stats.line_changes {
+      stats
+        .line_changes
+        .entry(date.clone())
+        .or_default()
+        .add(changes);
     }
 
-    weeks.push(json!({
-      "contributionDays": week_days
-    }));
+    let name = match path.strip_prefix(root) {
+      Ok(relative) if !relative.as_os_str().is_empty() => relative.display().to_string(),
+      _ => path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string()),
+    };
+    repo_commits.push((name, commits));
   }
+  repo_commits.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+  stats.languages = languages::percentages(languages);
+  let graph = ContributionGraph::from_weeks(&stats.contribution_graph);
+  (stats.current_streak, stats.longest_streak) = graph.calculate_streaks();
+  stats.total_contributions = graph.calculate_total_contributions();
+
+  let first_user = &analyzed[0].1;
+  let user_data = UserData {
+    login: first_user.login.clone(),
+    name: first_user.name.clone(),
+    email: first_user.email.clone(),
+    bio: Some(format!(
+      "Local workspace: {} ({} repositories)",
+      root.display(),
+      analyzed.len()
+    )),
+    ..Default::default()
+  };
 
-  Ok(json!({
-    "name": user_name,
-    "email": user_email,
-    "bio": format!("Local repository: {}", get_repo_path()?),
-    "company": "",
-    "website": "",
-    "total_repos": 1,
-    "total_stars": 0,
-    "total_forks": 0,
-    "languages": {},
-    "contribution_graph": weeks,
-    "current_streak": 0,
-    "longest_streak": 0,
-    "total_contributions": total_commits,
-    "pull_requests": {
-      "open": 0,
-      "awaiting_review": 0,
-      "mentions": 0
-    },
-    "issues": {
-      "assigned": 0,
-      "created": 0,
-      "mentions": 0
-    },
-  }))
+  Ok(Workspace {
+    user_data,
+    stats,
+    repos: repo_commits,
+  })
 }
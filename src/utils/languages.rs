@@ -0,0 +1,206 @@
+use crate::config::LanguageWeighting;
+use futures_util::stream::{self, StreamExt};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+
+/// Per-repository language requests in flight at once
+pub const CONCURRENT_REQUESTS: usize = 8;
+
+/// Linguist names for common source file extensions
+static EXTENSIONS: &[(&str, &str)] = &[
+  ("asm", "Assembly"),
+  ("bat", "Batchfile"),
+  ("c", "C"),
+  ("cc", "C++"),
+  ("clj", "Clojure"),
+  ("cmake", "CMake"),
+  ("cpp", "C++"),
+  ("cs", "C#"),
+  ("css", "CSS"),
+  ("cxx", "C++"),
+  ("dart", "Dart"),
+  ("elm", "Elm"),
+  ("erl", "Erlang"),
+  ("ex", "Elixir"),
+  ("exs", "Elixir"),
+  ("fs", "F#"),
+  ("gleam", "Gleam"),
+  ("go", "Go"),
+  ("groovy", "Groovy"),
+  ("h", "C"),
+  ("hpp", "C++"),
+  ("hs", "Haskell"),
+  ("htm", "HTML"),
+  ("html", "HTML"),
+  ("ipynb", "Jupyter Notebook"),
+  ("java", "Java"),
+  ("jl", "Julia"),
+  ("js", "JavaScript"),
+  ("jsx", "JavaScript"),
+  ("kt", "Kotlin"),
+  ("kts", "Kotlin"),
+  ("less", "Less"),
+  ("lua", "Lua"),
+  ("m", "Objective-C"),
+  ("mjs", "JavaScript"),
+  ("ml", "OCaml"),
+  ("mm", "Objective-C++"),
+  ("nim", "Nim"),
+  ("nix", "Nix"),
+  ("php", "PHP"),
+  ("pl", "Perl"),
+  ("ps1", "PowerShell"),
+  ("py", "Python"),
+  ("r", "R"),
+  ("rb", "Ruby"),
+  ("rkt", "Racket"),
+  ("rs", "Rust"),
+  ("sass", "Sass"),
+  ("scala", "Scala"),
+  ("scss", "SCSS"),
+  ("sh", "Shell"),
+  ("sol", "Solidity"),
+  ("sql", "TSQL"),
+  ("svelte", "Svelte"),
+  ("swift", "Swift"),
+  ("tex", "TeX"),
+  ("tf", "HCL"),
+  ("ts", "TypeScript"),
+  ("tsx", "TypeScript"),
+  ("v", "V"),
+  ("vim", "Vim Script"),
+  ("vue", "Vue"),
+  ("zig", "Zig"),
+  ("zsh", "Shell"),
+];
+
+/// Raw language amounts across a user's repositories, to be turned into
+/// shares with [`percentages`].
+///
+/// With byte weighting, `fetch` is called for every `(endpoint, weight)` in
+/// `requests` and each `{language: amount}` answer is scaled by its weight.
+/// Failed requests are skipped; when none succeed, the primary `language`
+/// of each repo in `repos` is counted instead.
+pub async fn language_stats<F, Fut>(
+  repos: &[Value],
+  weighting: LanguageWeighting,
+  requests: Vec<(String, f64)>,
+  fetch: F,
+) -> HashMap<String, f64>
+where
+  F: Fn(String) -> Fut,
+  Fut: Future<Output = anyhow::Result<Value>>,
+{
+  if weighting == LanguageWeighting::Bytes && !requests.is_empty() {
+    let breakdowns: Vec<Option<(Value, f64)>> = stream::iter(requests)
+      .map(|(endpoint, weight)| {
+        let request = fetch(endpoint);
+        async move { request.await.ok().map(|langs| (langs, weight)) }
+      })
+      .buffer_unordered(CONCURRENT_REQUESTS)
+      .collect()
+      .await;
+
+    let totals = breakdown_totals(breakdowns.iter().flatten().map(|(l, w)| (l, *w)));
+    if totals.values().sum::<f64>() > 0.0 {
+      return totals;
+    }
+  }
+
+  primary_language_counts(repos)
+}
+
+/// Percentages from `{language: amount}` maps, each scaled by its weight
+pub fn from_breakdowns<'a>(
+  breakdowns: impl IntoIterator<Item = (&'a Value, f64)>,
+) -> HashMap<String, f64> {
+  percentages(breakdown_totals(breakdowns))
+}
+
+/// Sums `{language: amount}` maps, each scaled by its weight
+fn breakdown_totals<'a>(
+  breakdowns: impl IntoIterator<Item = (&'a Value, f64)>,
+) -> HashMap<String, f64> {
+  let mut totals: HashMap<String, f64> = HashMap::new();
+
+  for (breakdown, weight) in breakdowns {
+    let Some(langs) = breakdown.as_object() else {
+      continue;
+    };
+    for (lang, amount) in langs {
+      if let Some(amount) = amount.as_f64() {
+        *totals.entry(lang.clone()).or_insert(0.0) += amount * weight;
+      }
+    }
+  }
+
+  totals
+}
+
+/// Scales `{language: amount}` totals to percentages
+pub fn percentages(mut totals: HashMap<String, f64>) -> HashMap<String, f64> {
+  let total: f64 = totals.values().sum();
+  if total <= 0.0 {
+    return HashMap::new();
+  }
+
+  for value in totals.values_mut() {
+    *value = *value / total * 100.0;
+  }
+
+  totals
+}
+
+/// Language of a source file, guessed from its name. Data, docs and config
+/// files aren't counted.
+pub fn language_for_path(path: &str) -> Option<&'static str> {
+  let file_name = path.rsplit('/').next().unwrap_or(path);
+  match file_name {
+    "Makefile" | "GNUmakefile" => return Some("Makefile"),
+    "Dockerfile" => return Some("Dockerfile"),
+    "CMakeLists.txt" => return Some("CMake"),
+    _ => {}
+  }
+
+  let (_, extension) = file_name.rsplit_once('.')?;
+  let extension = extension.to_ascii_lowercase();
+  EXTENSIONS
+    .iter()
+    .find(|(ext, _)| *ext == extension)
+    .map(|(_, language)| *language)
+}
+
+/// Percentages from each repo's primary `language`, one vote per repo
+pub fn by_primary_language(repos: &[Value]) -> HashMap<String, f64> {
+  percentages(primary_language_counts(repos))
+}
+
+/// Repos per primary `language`
+pub fn primary_language_counts(repos: &[Value]) -> HashMap<String, f64> {
+  let mut counts: HashMap<String, f64> = HashMap::new();
+
+  for language in repos.iter().filter_map(|repo| repo["language"].as_str()) {
+    if !language.is_empty() {
+      *counts.entry(display_name(language)).or_insert(0.0) += 1.0;
+    }
+  }
+
+  counts
+}
+
+/// The provider's spelling of a language, except that all-lowercase names
+/// (as Bitbucket reports them) take the linguist spelling when it's known,
+/// so "javascript" and GitHub's "JavaScript" count as one language
+fn display_name(language: &str) -> String {
+  if language.chars().any(char::is_uppercase) {
+    return language.to_string();
+  }
+
+  EXTENSIONS
+    .iter()
+    .map(|(_, name)| *name)
+    .find(|name| name.eq_ignore_ascii_case(language))
+    .unwrap_or(language)
+    .to_string()
+}
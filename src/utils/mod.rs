@@ -1,2 +1,4 @@
+pub mod calendar;
 pub mod git;
+pub mod languages;
 pub mod timeline;
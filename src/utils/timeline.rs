@@ -4,7 +4,7 @@ use std::process::Command;
 
 pub fn get_git_timeline_graph(vertical: bool) -> Result<String> {
   let output = Command::new("git")
-    .args(&[
+    .args([
       "--no-pager",
       "log",
       "--color=always",
@@ -86,8 +86,8 @@ pub fn get_git_timeline_graph(vertical: bool) -> Result<String> {
   let mut rotated = Vec::new();
   for col in (0..max_len).rev() {
     let mut new_row = Vec::new();
-    for row in 0..padded.len() {
-      new_row.push(padded[row][col].clone());
+    for row in &padded {
+      new_row.push(row[col].clone());
     }
     rotated.push(new_row);
   }
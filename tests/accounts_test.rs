@@ -0,0 +1,274 @@
+use chrono::{Duration, Utc};
+use gitfetch_rs::config::{AccountConfig, Config};
+use gitfetch_rs::display::ansi;
+use gitfetch_rs::display::formatter::{DisplayFormatter, VisualOptions};
+use gitfetch_rs::fetcher::accounts::{fetch_accounts, fetch_members, merge_accounts, AccountData};
+use gitfetch_rs::fetcher::gitea::GiteaFetcher;
+use gitfetch_rs::fetcher::FetchOptions;
+use gitfetch_rs::fetcher::Fetcher;
+use gitfetch_rs::models::{SearchItem, SearchResult, UserData, UserStats};
+use gitfetch_rs::utils::calendar::merge_calendars;
+use mockito::Matcher;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+fn account(name: &str, login: &str, stats: serde_json::Value) -> AccountData {
+  AccountData {
+    name: name.to_string(),
+    user_data: UserData {
+      login: login.to_string(),
+      followers: 2,
+      ..Default::default()
+    },
+    stats: serde_json::from_value(stats).unwrap(),
+  }
+}
+
+#[test]
+fn test_merge_calendars_sums_by_date() {
+  let a: UserStats = serde_json::from_value(json!({"contribution_graph": [
+    {"contributionDays": [
+      {"contributionCount": 1, "date": "2024-03-03"},
+      {"contributionCount": 2, "date": "2024-03-04"}
+    ]}
+  ]}))
+  .unwrap();
+  let b: UserStats = serde_json::from_value(json!({"contribution_graph": [
+    {"contributionDays": [
+      {"contributionCount": 5, "date": "2024-03-04"},
+      {"contributionCount": 1, "date": "2024-03-05"}
+    ]}
+  ]}))
+  .unwrap();
+
+  let merged = merge_calendars([
+    a.contribution_graph.as_slice(),
+    b.contribution_graph.as_slice(),
+  ]);
+
+  let days: Vec<_> = merged
+    .iter()
+    .flat_map(|w| &w.contribution_days)
+    .map(|d| (d.date.as_str(), d.contribution_count))
+    .collect();
+  assert_eq!(
+    days,
+    vec![("2024-03-03", 1), ("2024-03-04", 7), ("2024-03-05", 1)]
+  );
+}
+
+#[test]
+fn test_merge_accounts() {
+  let work = account(
+    "work",
+    "alice-corp",
+    json!({
+      "total_stars": 4,
+      "total_repos": 3,
+      "languages": {"Go": 100.0},
+      "language_totals": {"Go": 1000.0},
+      "contribution_graph": [{"contributionDays": [
+        {"contributionCount": 0, "date": "2024-03-03"},
+        {"contributionCount": 2, "date": "2024-03-04"}
+      ]}],
+      "pull_requests": {"open": {"total_count": 1, "items": [
+        {"title": "Deploy", "repo": "corp/infra", "url": "https://gitlab.example/corp/infra/-/merge_requests/1", "number": 1}
+      ]}}
+    }),
+  );
+  let home = account(
+    "home",
+    "alice",
+    json!({
+      "total_stars": 10,
+      "total_repos": 1,
+      "languages": {"Rust": 75.0, "Go": 25.0},
+      "language_totals": {"Rust": 3000.0, "Go": 1000.0},
+      "contribution_graph": [{"contributionDays": [
+        {"contributionCount": 3, "date": "2024-03-03"},
+        {"contributionCount": 1, "date": "2024-03-05"}
+      ]}],
+      "pull_requests": {"open": {"total_count": 4, "items": []}}
+    }),
+  );
+
+  let (user, stats) = merge_accounts(&[work, home]);
+
+  assert_eq!(user.login, "alice-corp");
+  assert_eq!(user.followers, 4);
+  assert_eq!(stats.total_stars, 14);
+  assert_eq!(stats.total_repos, 4);
+  assert_eq!(stats.total_contributions, 6);
+  assert_eq!(stats.current_streak, 3);
+  // Bytes add up across accounts, whatever their repo counts
+  assert_eq!(stats.languages["Go"], 40.0);
+  assert_eq!(stats.languages["Rust"], 60.0);
+
+  assert_eq!(stats.pull_requests.open.total_count, 5);
+  assert_eq!(
+    stats.pull_requests.open.items,
+    vec![SearchItem {
+      title: "Deploy".to_string(),
+      repo: "corp/infra".to_string(),
+      url: "https://gitlab.example/corp/infra/-/merge_requests/1".to_string(),
+      number: 1,
+      origin: Some("work".to_string()),
+    }]
+  );
+  assert_eq!(stats.issues.assigned, SearchResult::default());
+}
+
+#[test]
+fn test_merge_accounts_keeps_earliest_creation_instant() {
+  let created = |name: &str, created_at: &str| {
+    let mut data = account(name, name, json!({}));
+    data.user_data.created_at = Some(created_at.to_string());
+    data
+  };
+
+  // 10:00+02:00 is 08:00 UTC, earlier than 09:30 UTC though it sorts later
+  let (user, _) = merge_accounts(&[
+    created("gitlab", "2019-05-01T09:30:00.000Z"),
+    created("bitbucket", "2019-05-01T10:00:00+02:00"),
+    created("other", "sometime in 2010"),
+  ]);
+  assert_eq!(
+    user.created_at.as_deref(),
+    Some("2019-05-01T10:00:00+02:00")
+  );
+}
+
+async fn mock_gitea(server: &mut mockito::Server, login: &str, contributions: u32) {
+  let today = Utc::now().date_naive();
+  let timestamp = (today - Duration::days(1))
+    .and_hms_opt(12, 0, 0)
+    .unwrap()
+    .and_utc()
+    .timestamp();
+
+  server
+    .mock("GET", format!("/api/v1/users/{}", login).as_str())
+    .with_status(200)
+    .with_body(json!({"login": login, "followers_count": 1}).to_string())
+    .create_async()
+    .await;
+  server
+    .mock("GET", format!("/api/v1/users/{}/repos", login).as_str())
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(json!([{"stars_count": 2, "language": "Go"}]).to_string())
+    .create_async()
+    .await;
+  server
+    .mock("GET", format!("/api/v1/users/{}/heatmap", login).as_str())
+    .with_status(200)
+    .with_body(json!([{"timestamp": timestamp, "contributions": contributions}]).to_string())
+    .create_async()
+    .await;
+}
+
+#[tokio::test]
+async fn test_fetch_accounts_sorts_by_name_and_keeps_errors() {
+  let mut codeberg = mockito::Server::new_async().await;
+  let mut forge = mockito::Server::new_async().await;
+  mock_gitea(&mut codeberg, "alice", 3).await;
+  mock_gitea(&mut forge, "a.smith", 4).await;
+
+  let mut accounts = BTreeMap::new();
+  for (name, url, login) in [
+    ("codeberg", codeberg.url(), "alice"),
+    ("forge", forge.url(), "a.smith"),
+  ] {
+    accounts.insert(
+      name.to_string(),
+      AccountConfig {
+        provider: "gitea".to_string(),
+        provider_url: Some(url),
+        username: Some(login.to_string()),
+        ..Default::default()
+      },
+    );
+  }
+  accounts.insert(
+    "broken".to_string(),
+    AccountConfig {
+      provider: "gitea".to_string(),
+      ..Default::default()
+    },
+  );
+
+  let results = fetch_accounts(&accounts, &FetchOptions::default()).await;
+  let names: Vec<_> = results.iter().map(|(name, _)| name.as_str()).collect();
+  assert_eq!(names, vec!["broken", "codeberg", "forge"]);
+  assert!(results[0].1.is_err());
+
+  let fetched: Vec<AccountData> = results
+    .into_iter()
+    .filter_map(|(_, result)| result.ok())
+    .collect();
+  assert_eq!(fetched.len(), 2);
+
+  let (user, stats) = merge_accounts(&fetched);
+  assert_eq!(user.login, "alice");
+  assert_eq!(user.followers, 2);
+  assert_eq!(stats.total_stars, 4);
+  assert_eq!(stats.total_contributions, 7);
+}
+
+#[tokio::test]
+async fn test_fetch_members_with_bounded_concurrency() {
+  let mut server = mockito::Server::new_async().await;
+  mock_gitea(&mut server, "alice", 3).await;
+  mock_gitea(&mut server, "bob", 4).await;
+  mock_gitea(&mut server, "carol", 1).await;
+
+  let fetcher: Arc<dyn Fetcher> = Arc::new(GiteaFetcher::new(&server.url(), None).unwrap());
+  let members = vec!["carol".to_string(), "alice".to_string(), "bob".to_string()];
+  let results = fetch_members(fetcher, members, 2).await;
+
+  let totals: Vec<_> = results
+    .iter()
+    .map(|(login, result)| {
+      let data = result.as_ref().unwrap();
+      (login.as_str(), data.stats.total_contributions)
+    })
+    .collect();
+  assert_eq!(totals, vec![("carol", 1), ("alice", 3), ("bob", 4)]);
+}
+
+#[test]
+fn test_leaderboard_ranks_by_contributions() {
+  let calendar = |counts: &[u32]| -> UserStats {
+    let days: Vec<_> = counts
+      .iter()
+      .enumerate()
+      .map(
+        |(i, count)| json!({"contributionCount": count, "date": format!("2024-03-{:02}", i + 3)}),
+      )
+      .collect();
+    serde_json::from_value(json!({"contribution_graph": [{"contributionDays": days}]})).unwrap()
+  };
+  let alice = calendar(&[1, 1, 0, 2]);
+  let bob = calendar(&[5, 2, 3, 0]);
+  let carol = calendar(&[0, 1, 1, 2]);
+
+  let formatter = DisplayFormatter::new(Config::default(), VisualOptions::default()).unwrap();
+  let lines: Vec<String> = formatter
+    .leaderboard_lines(&[("alice", &alice), ("bob", &bob), ("carol", &carol)])
+    .iter()
+    .map(|line| {
+      ansi::parse(line)
+        .into_iter()
+        .map(|span| span.text)
+        .collect()
+    })
+    .collect();
+
+  assert_eq!(lines[0], "LEADERBOARD");
+  assert!(lines[3].contains("bob") && lines[3].contains("10"));
+  // alice and carol tie on contributions; carol's current streak is longer
+  assert!(lines[4].contains("carol") && lines[4].contains("3d"));
+  assert!(lines[5].contains("alice") && lines[5].contains("1d"));
+  assert!(lines[5].trim_start().starts_with('3'));
+}
@@ -0,0 +1,14 @@
+use gitfetch_rs::display::ansi;
+
+#[test]
+fn test_ansi_parse() {
+  let spans = ansi::parse("\x1b[38;2;118;215;161mName\x1b[0m - \x1b[1mBold\x1b[0m\x1b[91m!\x1b[0m");
+
+  assert_eq!(spans.len(), 4);
+  assert_eq!(spans[0].text, "Name");
+  assert_eq!(spans[0].fg, Some((118, 215, 161)));
+  assert_eq!(spans[1].text, " - ");
+  assert_eq!(spans[1].fg, None);
+  assert!(spans[2].bold);
+  assert_eq!(spans[3].fg, Some((255, 85, 85)));
+}
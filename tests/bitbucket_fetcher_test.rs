@@ -0,0 +1,305 @@
+use gitfetch_rs::fetcher::bitbucket::BitbucketFetcher;
+use gitfetch_rs::fetcher::{FetchOptions, Fetcher};
+use gitfetch_rs::utils::calendar::DateRange;
+use mockito::Matcher;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_data_center_authenticated_user() {
+  let mut server = mockito::Server::new_async().await;
+  server
+    .mock("GET", "/rest/api/1.0/application-properties")
+    .match_header("authorization", "Bearer test-token")
+    .with_status(200)
+    .with_header("x-ausername", "alice")
+    .with_body("{}")
+    .create_async()
+    .await;
+
+  let fetcher = BitbucketFetcher::new(&server.url(), Some("test-token")).unwrap();
+  assert_eq!(fetcher.get_authenticated_user().await.unwrap(), "alice");
+}
+
+#[tokio::test]
+async fn test_data_center_stats() {
+  let mut server = mockito::Server::new_async().await;
+
+  server
+    .mock("GET", "/rest/api/1.0/application-properties")
+    .with_status(200)
+    .with_header("x-ausername", "alice")
+    .with_body("{}")
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/rest/api/1.0/projects/~alice/repos")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(
+      json!({
+        "values": [{"slug": "tools"}, {"slug": "notes"}],
+        "isLastPage": true
+      })
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/rest/api/1.0/dashboard/pull-requests")
+    .match_query(Matcher::UrlEncoded("role".into(), "REVIEWER".into()))
+    .with_status(200)
+    .with_body(
+      json!({
+        "values": [{
+          "id": 17,
+          "title": "Bump dependencies",
+          "toRef": {"repository": {"slug": "api", "project": {"key": "PLAT"}}},
+          "links": {"self": [{"href": "https://bitbucket.corp/projects/PLAT/repos/api/pull-requests/17"}]}
+        }],
+        "isLastPage": true
+      })
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/rest/api/1.0/dashboard/pull-requests")
+    .match_query(Matcher::UrlEncoded("role".into(), "AUTHOR".into()))
+    .with_status(200)
+    .with_body(json!({"values": [], "isLastPage": true}).to_string())
+    .create_async()
+    .await;
+
+  let fetcher = BitbucketFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let stats = fetcher.fetch_user_stats("alice", None).await.unwrap();
+
+  assert_eq!(stats.total_repos, 2);
+
+  let awaiting = &stats.pull_requests.awaiting_review;
+  assert_eq!(awaiting.total_count, 1);
+  assert_eq!(awaiting.items[0].repo, "PLAT/api");
+  assert_eq!(awaiting.items[0].number, 17);
+  assert_eq!(stats.pull_requests.open.total_count, 0);
+}
+
+#[tokio::test]
+async fn test_data_center_counts_follow_pages() {
+  let mut server = mockito::Server::new_async().await;
+
+  server
+    .mock("GET", "/rest/api/1.0/application-properties")
+    .with_status(200)
+    .with_header("x-ausername", "alice")
+    .with_body("{}")
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/rest/api/1.0/projects/~alice/repos")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(
+      json!({
+        "values": [{"slug": "tools", "project": {"key": "~ALICE"}}],
+        "isLastPage": true
+      })
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  // Two pages each of forks and authored pull requests
+  let page = |start: &str, values: serde_json::Value, next: Option<u64>| {
+    let mut body = json!({"values": values, "isLastPage": next.is_none()});
+    if let Some(next) = next {
+      body["nextPageStart"] = json!(next);
+    }
+    (start.to_string(), body.to_string())
+  };
+  let pr = json!({"id": 1, "title": "Change", "toRef": {"repository": {"slug": "tools", "project": {"key": "~ALICE"}}}});
+  for (path, pages) in [
+    (
+      "/rest/api/1.0/projects/~ALICE/repos/tools/forks",
+      vec![
+        page("0", json!([{"slug": "a"}, {"slug": "b"}]), Some(2)),
+        page("2", json!([{"slug": "c"}]), None),
+      ],
+    ),
+    (
+      "/rest/api/1.0/dashboard/pull-requests",
+      vec![
+        page("0", json!([pr, pr]), Some(2)),
+        page("2", json!([pr]), None),
+      ],
+    ),
+  ] {
+    for (start, body) in pages {
+      server
+        .mock("GET", path)
+        .match_query(Matcher::UrlEncoded("start".into(), start))
+        .with_status(200)
+        .with_body(body)
+        .create_async()
+        .await;
+    }
+  }
+
+  let fetcher = BitbucketFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let stats = fetcher.fetch_user_stats("alice", None).await.unwrap();
+
+  assert_eq!(stats.total_forks, 3);
+  assert_eq!(stats.pull_requests.open.total_count, 3);
+  assert_eq!(stats.pull_requests.awaiting_review.total_count, 3);
+}
+
+#[tokio::test]
+async fn test_cloud_languages_from_repositories() {
+  let mut server = mockito::Server::new_async().await;
+  let api_base = format!("{}/2.0", server.url());
+
+  server
+    .mock("GET", "/2.0/user")
+    .with_status(200)
+    .with_body(json!({"username": "bob"}).to_string())
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/2.0/repositories/alice")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(
+      json!({
+        "values": [{"language": "rust"}, {"language": "rust"}, {"language": "python"}, {"language": ""}]
+      })
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/2.0/pullrequests/alice")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(json!({"size": 0, "values": []}).to_string())
+    .create_async()
+    .await;
+
+  let fetcher = BitbucketFetcher::new(&api_base, Some("bob:app-password")).unwrap();
+  let stats = fetcher.fetch_user_stats("alice", None).await.unwrap();
+
+  assert_eq!(stats.total_repos, 4);
+
+  let rust = stats.languages["Rust"];
+  assert!((rust - 200.0 / 3.0).abs() < 0.01);
+  assert!(stats.languages.contains_key("Python"));
+}
+
+#[tokio::test]
+async fn test_cloud_forks_follow_next_pages() {
+  let mut server = mockito::Server::new_async().await;
+  let api_base = format!("{}/2.0", server.url());
+
+  server
+    .mock("GET", "/2.0/user")
+    .with_status(200)
+    .with_body(json!({"username": "alice"}).to_string())
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/2.0/repositories/alice")
+    .match_query(Matcher::UrlEncoded("pagelen".into(), "100".into()))
+    .with_status(200)
+    .with_body(
+      json!({
+        "values": [{"full_name": "alice/tools"}],
+        "next": format!("{}/repositories/alice?page=2", api_base)
+      })
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  let second_page = server
+    .mock("GET", "/2.0/repositories/alice")
+    .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+    .match_header("authorization", Matcher::Regex("^Basic ".into()))
+    .with_status(200)
+    .with_body(json!({"values": [{"full_name": "alice/notes"}]}).to_string())
+    .create_async()
+    .await;
+
+  for (repo, size) in [("tools", 3), ("notes", 1)] {
+    server
+      .mock(
+        "GET",
+        format!("/2.0/repositories/alice/{}/forks", repo).as_str(),
+      )
+      .match_query(Matcher::Any)
+      .with_status(200)
+      .with_body(json!({"size": size, "values": []}).to_string())
+      .create_async()
+      .await;
+  }
+
+  server
+    .mock("GET", "/2.0/pullrequests/alice")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(json!({"size": 0, "values": []}).to_string())
+    .create_async()
+    .await;
+
+  let fetcher = BitbucketFetcher::new(&api_base, Some("alice:app-password")).unwrap();
+  let stats = fetcher.fetch_user_stats("alice", None).await.unwrap();
+
+  second_page.assert_async().await;
+  assert_eq!(stats.total_repos, 2);
+  assert_eq!(stats.total_forks, 4);
+}
+
+#[tokio::test]
+async fn test_cloud_refuses_next_links_to_other_hosts() {
+  let mut server = mockito::Server::new_async().await;
+  let api_base = format!("{}/2.0", server.url());
+
+  server
+    .mock("GET", "/2.0/repositories/alice")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(
+      json!({
+        "values": [],
+        "next": "https://attacker.example/2.0/repositories/alice?page=2"
+      })
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  let fetcher = BitbucketFetcher::new(&api_base, Some("alice:app-password")).unwrap();
+  let error = fetcher
+    .fetch_user_stats("alice", None)
+    .await
+    .unwrap_err()
+    .to_string();
+
+  assert!(error.contains("another host"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_user_stats_reject_date_range() {
+  let options = FetchOptions {
+    range: Some(DateRange::year(2023).unwrap()),
+    ..Default::default()
+  };
+  let fetcher = BitbucketFetcher::new("https://bitbucket.example.com", None)
+    .unwrap()
+    .with_options(options);
+
+  let err = fetcher.fetch_user_stats("alice", None).await.unwrap_err();
+  assert!(err.to_string().contains("--year/--from/--to"));
+}
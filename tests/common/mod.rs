@@ -0,0 +1,56 @@
+//! Sample account shared by the rendering and export tests
+
+use gitfetch_rs::models::{
+  ContributionDay, ContributionWeek, PullRequestStats, SearchItem, SearchResult, UserData,
+  UserStats,
+};
+
+pub fn sample_user() -> UserData {
+  UserData {
+    login: "octocat".to_string(),
+    name: Some("Octo <Cat>".to_string()),
+    blog: Some("https://octo.example".to_string()),
+    ..Default::default()
+  }
+}
+
+/// Two calendar weeks spanning January and February 2024, plus one open
+/// pull request whose title needs escaping
+pub fn sample_stats() -> UserStats {
+  let week = |days: &[(&str, u32)]| ContributionWeek {
+    contribution_days: days
+      .iter()
+      .map(|&(date, count)| ContributionDay {
+        contribution_count: count,
+        date: date.to_string(),
+      })
+      .collect(),
+  };
+
+  UserStats {
+    total_stars: 12,
+    total_contributions: 6,
+    current_streak: 2,
+    languages: [("Rust".to_string(), 75.0), ("Go".to_string(), 25.0)]
+      .into_iter()
+      .collect(),
+    contribution_graph: vec![
+      week(&[("2024-01-28", 0), ("2024-01-29", 1)]),
+      week(&[("2024-02-04", 5)]),
+    ],
+    pull_requests: PullRequestStats {
+      open: SearchResult {
+        total_count: 2,
+        items: vec![SearchItem {
+          title: "Fix <script> & escape".to_string(),
+          repo: "octo/repo".to_string(),
+          url: "https://github.com/octo/repo/pull/7".to_string(),
+          number: 7,
+          origin: None,
+        }],
+      },
+      ..Default::default()
+    },
+    ..Default::default()
+  }
+}
@@ -1,4 +1,4 @@
-use gitfetch_rs::config::ColorConfig;
+use gitfetch_rs::config::{ColorConfig, ConfigManager};
 
 #[test]
 fn test_color_config_default() {
@@ -43,3 +43,160 @@ fn test_color_config_levels_different() {
   assert_ne!(config.level_2, config.level_3);
   assert_ne!(config.level_3, config.level_4);
 }
+
+#[test]
+fn test_accounts_config_parses() {
+  let config: gitfetch_rs::config::Config = toml::from_str(
+    r##"
+cache_expiry_minutes = 15
+
+[colors]
+level_0 = "#ebedf0"
+level_1 = "#9be9a8"
+level_2 = "#40c463"
+level_3 = "#30a14e"
+level_4 = "#216e39"
+
+[accounts.work]
+provider = "gitlab"
+provider_url = "https://gitlab.example.com"
+
+[accounts.home]
+provider = "github"
+username = "alice"
+"##,
+  )
+  .unwrap();
+
+  assert!(config.provider.is_none());
+  assert_eq!(config.accounts.len(), 2);
+  assert_eq!(config.accounts["work"].provider, "gitlab");
+  assert_eq!(config.accounts["home"].username.as_deref(), Some("alice"));
+  assert!(config.accounts["home"].provider_url.is_none());
+}
+
+const PROFILES_CONFIG: &str = r##"
+provider = "github"
+token = "ghp_personal"
+default_username = "alice"
+cache_expiry_minutes = 15
+default_profile = "personal"
+author_aliases = ["alice@home.lan"]
+
+[colors]
+level_0 = "#ebedf0"
+level_1 = "#9be9a8"
+level_2 = "#40c463"
+level_3 = "#30a14e"
+level_4 = "#216e39"
+
+[profiles.personal]
+
+[profiles.work]
+provider = "gitlab"
+provider_url = "https://gitlab.example.com"
+default_username = "a.smith"
+cache_expiry_minutes = 60
+author_aliases = ["a.smith@corp.example", "Alice Smith"]
+
+[profiles.work.colors]
+level_0 = "#161b22"
+level_1 = "#0e4429"
+level_2 = "#006d32"
+level_3 = "#26a641"
+level_4 = "#39d353"
+"##;
+
+#[test]
+fn test_profile_overrides_top_level() {
+  let config: gitfetch_rs::config::Config = toml::from_str(PROFILES_CONFIG).unwrap();
+
+  let work = config.with_profile("work").unwrap();
+  assert_eq!(work.provider.as_deref(), Some("gitlab"));
+  assert_eq!(work.default_username.as_deref(), Some("a.smith"));
+  assert_eq!(work.cache_expiry_minutes, 60);
+  assert_eq!(work.colors.level_0, "#161b22");
+  assert_eq!(work.author_aliases, ["a.smith@corp.example", "Alice Smith"]);
+  // Tokens belong to a provider, so one for GitHub isn't reused on GitLab
+  assert_eq!(work.token, None);
+
+  let personal = config.with_profile("personal").unwrap();
+  assert_eq!(personal.provider.as_deref(), Some("github"));
+  assert_eq!(personal.token.as_deref(), Some("ghp_personal"));
+  assert_eq!(personal.author_aliases, ["alice@home.lan"]);
+  assert!(config.with_profile("missing").is_none());
+}
+
+#[test]
+fn test_config_manager_profiles() {
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("config.toml");
+  std::fs::write(&path, PROFILES_CONFIG).unwrap();
+
+  let mut manager = ConfigManager::load(path.clone()).unwrap();
+  manager.use_profile(None, false).unwrap();
+  assert_eq!(manager.get_profile(), Some("personal"));
+
+  let mut manager = ConfigManager::load(path.clone()).unwrap();
+  manager.use_profile(Some("work"), false).unwrap();
+  assert_eq!(manager.get_provider(), Some("gitlab"));
+
+  let mut manager = ConfigManager::load(path.clone()).unwrap();
+  let err = manager.use_profile(Some("oss"), false).unwrap_err();
+  assert!(err.to_string().contains("personal, work"));
+
+  // Creating a profile saves into its own table and keeps the top level
+  manager.use_profile(Some("oss"), true).unwrap();
+  assert!(!manager.is_initialized());
+  manager.set_provider("gitea".to_string());
+  manager.set_provider_url("https://codeberg.org".to_string());
+  manager.save().unwrap();
+
+  let saved: gitfetch_rs::config::Config =
+    toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+  assert_eq!(saved.provider.as_deref(), Some("github"));
+  assert_eq!(saved.profiles["oss"].provider.as_deref(), Some("gitea"));
+  assert_eq!(saved.profiles["work"].cache_expiry_minutes, Some(60));
+  assert_eq!(saved.default_profile.as_deref(), Some("personal"));
+}
+
+#[test]
+fn test_profile_with_own_provider_does_not_inherit_credentials() {
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("config.toml");
+  let config = format!(
+    "{}\n[profiles.oss]\nprovider = \"gitlab\"\n",
+    PROFILES_CONFIG
+  );
+  std::fs::write(&path, config).unwrap();
+
+  let mut manager = ConfigManager::load(path.clone()).unwrap();
+  manager.use_profile(Some("oss"), false).unwrap();
+  assert_eq!(manager.get_provider(), Some("gitlab"));
+  assert_eq!(manager.get_provider_url(), None);
+  assert_eq!(manager.get_token(), None);
+  assert_eq!(manager.get_default_username(), None);
+
+  // --change-provider on the profile must not copy the GitHub token into it
+  manager.set_provider_url("https://gitlab.example.com".to_string());
+  manager.save().unwrap();
+
+  let saved: gitfetch_rs::config::Config =
+    toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+  assert_eq!(saved.profiles["oss"].token, None);
+  assert_eq!(saved.profiles["oss"].default_username, None);
+  assert_eq!(saved.token.as_deref(), Some("ghp_personal"));
+}
+
+#[test]
+fn test_load_rejects_unparsable_config() {
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("config.toml");
+  // Accounts need a provider; the rest of the file must not be thrown away
+  let content = "provider = \"github\"\n\n[accounts.work]\nusername = \"alice\"\n";
+  std::fs::write(&path, content).unwrap();
+
+  let err = ConfigManager::load(path.clone()).err().unwrap();
+  assert!(err.to_string().contains(&path.display().to_string()));
+  assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+}
@@ -0,0 +1,60 @@
+use gitfetch_rs::display::export::{ExportDocument, SCHEMA_VERSION};
+use gitfetch_rs::display::OutputFormat;
+use serde_json::json;
+
+mod common;
+
+#[test]
+fn test_json_document() {
+  let user = common::sample_user();
+  let stats = common::sample_stats();
+  let output = ExportDocument::new("octocat", &user, &stats)
+    .render(OutputFormat::Json)
+    .unwrap();
+  let doc: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+  assert_eq!(doc["schema_version"], SCHEMA_VERSION);
+  assert_eq!(doc["username"], "octocat");
+  assert_eq!(doc["user"]["name"], "Octo <Cat>");
+  assert_eq!(doc["stats"]["current_streak"], 2);
+  assert_eq!(doc["stats"]["languages"]["Rust"], 75.0);
+  assert_eq!(
+    doc["stats"]["calendar"][1],
+    json!({"date": "2024-01-29", "count": 1})
+  );
+  assert_eq!(
+    doc["stats"]["pull_requests"]["open"]["items"][0]["number"],
+    7
+  );
+  assert_eq!(doc["stats"]["issues"]["assigned"]["total_count"], 0);
+}
+
+#[test]
+fn test_yaml_document() {
+  let user = common::sample_user();
+  let stats = common::sample_stats();
+  let output = ExportDocument::new("octocat", &user, &stats)
+    .render(OutputFormat::Yaml)
+    .unwrap();
+
+  assert!(output.contains(&format!("schema_version: {}", SCHEMA_VERSION)));
+  assert!(output.contains("username: octocat"));
+  assert!(output.contains("date: 2024-01-29"));
+}
+
+#[test]
+fn test_toml_document() {
+  let user = common::sample_user();
+  let stats = common::sample_stats();
+  let output = ExportDocument::new("octocat", &user, &stats)
+    .render(OutputFormat::Toml)
+    .unwrap();
+  let doc: toml::Value = toml::from_str(&output).unwrap();
+
+  assert_eq!(
+    doc["schema_version"].as_integer(),
+    Some(SCHEMA_VERSION as i64)
+  );
+  assert_eq!(doc["stats"]["total_stars"].as_integer(), Some(12));
+  assert_eq!(doc["stats"]["calendar"][1]["count"].as_integer(), Some(1));
+}
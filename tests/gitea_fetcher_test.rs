@@ -0,0 +1,186 @@
+use chrono::{Duration, Utc};
+use gitfetch_rs::fetcher::gitea::GiteaFetcher;
+use gitfetch_rs::fetcher::{FetchOptions, Fetcher};
+use gitfetch_rs::models::UserData;
+use gitfetch_rs::utils::calendar::DateRange;
+use mockito::Matcher;
+use serde_json::json;
+
+fn days_ago_timestamp(days: i64) -> i64 {
+  (Utc::now().date_naive() - Duration::days(days))
+    .and_hms_opt(12, 0, 0)
+    .unwrap()
+    .and_utc()
+    .timestamp()
+}
+
+async fn mock_repos(server: &mut mockito::Server) {
+  server
+    .mock("GET", "/api/v1/users/alice/repos")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(json!([{"stars_count": 3, "forks_count": 2, "language": "Go"}]).to_string())
+    .create_async()
+    .await;
+}
+
+#[tokio::test]
+async fn test_contribution_graph_from_heatmap() {
+  let mut server = mockito::Server::new_async().await;
+  mock_repos(&mut server).await;
+
+  server
+    .mock("GET", "/api/v1/users/alice/heatmap")
+    .with_status(200)
+    .with_body(
+      json!([
+        {"timestamp": days_ago_timestamp(0), "contributions": 2},
+        {"timestamp": days_ago_timestamp(0) + 900, "contributions": 1},
+        {"timestamp": days_ago_timestamp(1), "contributions": 4},
+        {"timestamp": days_ago_timestamp(10), "contributions": 1}
+      ])
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  let fetcher = GiteaFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let user = UserData {
+    login: "alice".to_string(),
+    ..Default::default()
+  };
+  let stats = fetcher
+    .fetch_user_stats("alice", Some(&user))
+    .await
+    .unwrap();
+
+  let weeks = &stats.contribution_graph;
+  assert!(weeks.len() >= 52);
+  assert_eq!(weeks[0].contribution_days[0].contribution_count, 0);
+
+  assert_eq!(stats.total_contributions, 8);
+  assert_eq!(stats.current_streak, 2);
+  assert_eq!(stats.longest_streak, 2);
+  assert_eq!(stats.total_stars, 3);
+}
+
+#[tokio::test]
+async fn test_missing_heatmap_yields_empty_graph() {
+  let mut server = mockito::Server::new_async().await;
+  mock_repos(&mut server).await;
+
+  server
+    .mock("GET", "/api/v1/users/alice/heatmap")
+    .with_status(404)
+    .create_async()
+    .await;
+
+  let fetcher = GiteaFetcher::new(&server.url(), None).unwrap();
+  let user = UserData {
+    login: "alice".to_string(),
+    ..Default::default()
+  };
+  let stats = fetcher
+    .fetch_user_stats("alice", Some(&user))
+    .await
+    .unwrap();
+
+  assert!(stats.contribution_graph.is_empty());
+  assert_eq!(stats.total_contributions, 0);
+}
+
+#[tokio::test]
+async fn test_range_before_heatmap_window_is_refused() {
+  let mut server = mockito::Server::new_async().await;
+  mock_repos(&mut server).await;
+
+  let heatmap = server
+    .mock("GET", "/api/v1/users/alice/heatmap")
+    .with_status(200)
+    .with_body(json!([{"timestamp": days_ago_timestamp(0), "contributions": 3}]).to_string())
+    .expect(0)
+    .create_async()
+    .await;
+
+  let fetcher = GiteaFetcher::new(&server.url(), None)
+    .unwrap()
+    .with_options(FetchOptions {
+      range: Some(DateRange::year(2020).unwrap()),
+      ..Default::default()
+    });
+  let user = UserData {
+    login: "alice".to_string(),
+    ..Default::default()
+  };
+  let stats = fetcher
+    .fetch_user_stats("alice", Some(&user))
+    .await
+    .unwrap();
+
+  heatmap.assert_async().await;
+  assert!(stats.contribution_graph.is_empty());
+}
+
+#[tokio::test]
+async fn test_pull_request_and_issue_stats() {
+  let mut server = mockito::Server::new_async().await;
+  mock_repos(&mut server).await;
+
+  server
+    .mock("GET", "/api/v1/users/alice/heatmap")
+    .with_status(200)
+    .with_body("[]")
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/api/v1/user")
+    .with_status(200)
+    .with_body(json!({"login": "alice"}).to_string())
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/api/v1/repos/issues/search")
+    .match_query(Matcher::AllOf(vec![
+      Matcher::UrlEncoded("type".into(), "pulls".into()),
+      Matcher::UrlEncoded("review_requested".into(), "true".into()),
+    ]))
+    .with_status(200)
+    .with_header("x-total-count", "6")
+    .with_body(
+      json!([{
+        "title": "Add heatmap",
+        "number": 14,
+        "html_url": "https://codeberg.org/team/app/pulls/14",
+        "repository": {"full_name": "team/app"}
+      }])
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/api/v1/repos/issues/search")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body("[]")
+    .create_async()
+    .await;
+
+  let fetcher = GiteaFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let user = UserData {
+    login: "alice".to_string(),
+    ..Default::default()
+  };
+  let stats = fetcher
+    .fetch_user_stats("alice", Some(&user))
+    .await
+    .unwrap();
+
+  let awaiting = &stats.pull_requests.awaiting_review;
+  assert_eq!(awaiting.total_count, 6);
+  assert_eq!(awaiting.items[0].repo, "team/app");
+  assert_eq!(awaiting.items[0].number, 14);
+  assert_eq!(stats.issues.assigned.total_count, 0);
+}
@@ -0,0 +1,368 @@
+use gitfetch_rs::config::LanguageWeighting;
+use gitfetch_rs::fetcher::github::{resolve_api_urls, GitHubFetcher};
+use gitfetch_rs::fetcher::{FetchOptions, Fetcher};
+use gitfetch_rs::utils::calendar::DateRange;
+use mockito::Matcher;
+use serde_json::json;
+
+#[test]
+fn test_resolve_api_urls_github_com() {
+  let expected = (
+    "https://api.github.com".to_string(),
+    "https://api.github.com/graphql".to_string(),
+  );
+
+  assert_eq!(resolve_api_urls("https://github.com"), expected);
+  assert_eq!(resolve_api_urls("https://api.github.com/"), expected);
+  assert_eq!(resolve_api_urls(""), expected);
+}
+
+#[test]
+fn test_resolve_api_urls_enterprise() {
+  let expected = (
+    "https://ghe.corp/api/v3".to_string(),
+    "https://ghe.corp/api/graphql".to_string(),
+  );
+
+  assert_eq!(resolve_api_urls("https://ghe.corp"), expected);
+  assert_eq!(resolve_api_urls("https://ghe.corp/api/v3"), expected);
+  assert_eq!(resolve_api_urls("https://ghe.corp/api/v3/"), expected);
+}
+
+#[tokio::test]
+async fn test_get_authenticated_user() {
+  let mut server = mockito::Server::new_async().await;
+  let mock = server
+    .mock("GET", "/api/v3/user")
+    .match_header("authorization", "Bearer test-token")
+    .with_status(200)
+    .with_body(r#"{"login": "octocat"}"#)
+    .create_async()
+    .await;
+
+  let fetcher = GitHubFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let login = fetcher.get_authenticated_user().await.unwrap();
+
+  assert_eq!(login, "octocat");
+  mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_enterprise_host_never_gets_github_token() {
+  // Nothing else in this test binary reads these variables
+  std::env::set_var("GITHUB_TOKEN", "github-com-token");
+  std::env::remove_var("GH_ENTERPRISE_TOKEN");
+
+  let mut server = mockito::Server::new_async().await;
+  let mock = server
+    .mock("GET", "/api/v3/users/octocat")
+    .match_header("authorization", Matcher::Missing)
+    .with_status(200)
+    .with_body(r#"{"login": "octocat"}"#)
+    .create_async()
+    .await;
+
+  let fetcher = GitHubFetcher::new(&server.url(), None).unwrap();
+  let user = fetcher.fetch_user_data("octocat").await.unwrap();
+
+  assert_eq!(user.login, "octocat");
+  mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_fetch_user_data_error_status() {
+  let mut server = mockito::Server::new_async().await;
+  server
+    .mock("GET", "/api/v3/users/ghost")
+    .with_status(404)
+    .create_async()
+    .await;
+
+  let fetcher = GitHubFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let result = fetcher.fetch_user_data("ghost").await;
+
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_fetch_user_stats() {
+  let mut server = mockito::Server::new_async().await;
+
+  server
+    .mock("GET", "/api/v3/users/octocat/repos")
+    .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+    .with_status(200)
+    .with_body(
+      json!([
+        {"stargazers_count": 10, "forks_count": 2, "language": "Rust"},
+        {"stargazers_count": 5, "forks_count": 1, "language": "Go"}
+      ])
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  server
+    .mock("POST", "/api/graphql")
+    .with_status(200)
+    .with_body(
+      json!({
+        "data": {"user": {"contributionsCollection": {"contributionCalendar": {"weeks": [
+          {"contributionDays": [
+            {"contributionCount": 0, "date": "2024-01-01"},
+            {"contributionCount": 3, "date": "2024-01-02"},
+            {"contributionCount": 4, "date": "2024-01-03"}
+          ]}
+        ]}}}}
+      })
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/api/v3/user")
+    .with_status(200)
+    .with_body(r#"{"login": "octocat"}"#)
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/api/v3/search/issues")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(
+      json!({
+        "total_count": 7,
+        "items": [{
+          "title": "Fix the thing",
+          "number": 42,
+          "html_url": "https://github.com/octo/repo/pull/42",
+          "repository_url": "https://api.github.com/repos/octo/repo"
+        }]
+      })
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  let fetcher = GitHubFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let stats = fetcher.fetch_user_stats("octocat", None).await.unwrap();
+
+  assert_eq!(stats.total_stars, 15);
+  assert_eq!(stats.total_forks, 3);
+  assert_eq!(stats.total_repos, 2);
+  assert_eq!(stats.total_contributions, 7);
+  assert_eq!(stats.current_streak, 2);
+  assert_eq!(stats.pull_requests.open.total_count, 7);
+  assert_eq!(stats.pull_requests.open.items[0].repo, "octo/repo");
+  assert_eq!(stats.issues.assigned.items[0].number, 42);
+}
+
+#[tokio::test]
+async fn test_fetch_org_members_paginates() {
+  let mut server = mockito::Server::new_async().await;
+  let first_page: Vec<_> = (0..100)
+    .map(|i| json!({"login": format!("member{}", i)}))
+    .collect();
+
+  server
+    .mock("GET", "/api/v3/orgs/acme/members")
+    .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+    .with_status(200)
+    .with_body(json!(first_page).to_string())
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/api/v3/orgs/acme/members")
+    .match_query(Matcher::UrlEncoded("page".into(), "2".into()))
+    .with_status(200)
+    .with_body(json!([{"login": "last"}]).to_string())
+    .create_async()
+    .await;
+
+  let fetcher = GitHubFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let members = fetcher.fetch_org_members("acme").await.unwrap();
+
+  assert_eq!(members.len(), 101);
+  assert_eq!(members[0], "member0");
+  assert_eq!(members[100], "last");
+}
+
+async fn mock_language_repos(server: &mut mockito::Server) {
+  server
+    .mock("GET", "/api/v3/users/octocat/repos")
+    .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+    .with_status(200)
+    .with_body(
+      json!([
+        {"full_name": "octocat/big", "language": "Rust"},
+        {"full_name": "octocat/small", "language": "Go"}
+      ])
+      .to_string(),
+    )
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/api/v3/repos/octocat/big/languages")
+    .with_status(200)
+    .with_body(json!({"Rust": 8000, "Shell": 1000}).to_string())
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/api/v3/repos/octocat/small/languages")
+    .with_status(200)
+    .with_body(json!({"Go": 1000}).to_string())
+    .create_async()
+    .await;
+}
+
+#[tokio::test]
+async fn test_fetch_user_stats_language_weighting() {
+  let mut server = mockito::Server::new_async().await;
+  mock_language_repos(&mut server).await;
+
+  let fetcher = GitHubFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let stats = fetcher.fetch_user_stats("octocat", None).await.unwrap();
+  assert_eq!(stats.languages["Rust"], 80.0);
+  assert_eq!(stats.languages["Shell"], 10.0);
+  assert_eq!(stats.languages["Go"], 10.0);
+
+  let fetcher = GitHubFetcher::new(&server.url(), Some("test-token"))
+    .unwrap()
+    .with_options(FetchOptions {
+      language_weighting: LanguageWeighting::Repos,
+      ..Default::default()
+    });
+  let stats = fetcher.fetch_user_stats("octocat", None).await.unwrap();
+  assert_eq!(stats.languages["Rust"], 50.0);
+  assert_eq!(stats.languages["Go"], 50.0);
+  assert!(!stats.languages.contains_key("Shell"));
+}
+
+/// Calendar query for one `from`/`to` window answering with `days`
+async fn mock_calendar_chunk(
+  server: &mut mockito::Server,
+  from: &str,
+  to: &str,
+  days: serde_json::Value,
+) -> mockito::Mock {
+  server
+    .mock("POST", "/api/graphql")
+    .match_body(Matcher::PartialJson(json!({
+      "variables": {"login": "octocat", "from": from, "to": to}
+    })))
+    .with_status(200)
+    .with_body(
+      json!({
+        "data": {"user": {"contributionsCollection": {"contributionCalendar": {"weeks": [
+          {"contributionDays": days}
+        ]}}}}
+      })
+      .to_string(),
+    )
+    .create_async()
+    .await
+}
+
+#[tokio::test]
+async fn test_fetch_user_stats_date_range() {
+  let mut server = mockito::Server::new_async().await;
+  mock_language_repos(&mut server).await;
+
+  // 2024 has 366 days, one more than a single query may span. Days a
+  // chunk answers for outside its own window are ignored.
+  let first = mock_calendar_chunk(
+    &mut server,
+    "2024-01-01T00:00:00Z",
+    "2024-12-30T23:59:59Z",
+    json!([
+      {"contributionCount": 2, "date": "2024-12-30"},
+      {"contributionCount": 5, "date": "2024-12-31"}
+    ]),
+  )
+  .await;
+  let last = mock_calendar_chunk(
+    &mut server,
+    "2024-12-31T00:00:00Z",
+    "2024-12-31T23:59:59Z",
+    json!([{"contributionCount": 1, "date": "2024-12-31"}]),
+  )
+  .await;
+
+  let fetcher = GitHubFetcher::new(&server.url(), Some("test-token"))
+    .unwrap()
+    .with_options(FetchOptions {
+      range: Some(DateRange::year(2024).unwrap()),
+      ..Default::default()
+    });
+  let stats = fetcher.fetch_user_stats("octocat", None).await.unwrap();
+
+  first.assert_async().await;
+  last.assert_async().await;
+  assert_eq!(stats.total_contributions, 3);
+  assert_eq!(stats.current_streak, 2);
+  let first_day = &stats.contribution_graph[0].contribution_days[0];
+  assert_eq!(first_day.date, "2023-12-31");
+}
+
+#[tokio::test]
+async fn test_fetch_user_stats_include_private() {
+  let mut server = mockito::Server::new_async().await;
+  mock_language_repos(&mut server).await;
+
+  server
+    .mock("GET", "/api/v3/user")
+    .with_status(200)
+    .with_body(r#"{"login": "OctoCat"}"#)
+    .create_async()
+    .await;
+
+  let viewer = server
+    .mock("POST", "/api/graphql")
+    .match_body(Matcher::Regex("viewer".to_string()))
+    .with_status(200)
+    .with_body(
+      json!({
+        "data": {"viewer": {"contributionsCollection": {
+          "restrictedContributionsCount": 5,
+          "contributionCalendar": {"weeks": [
+            {"contributionDays": [
+              {"contributionCount": 4, "date": "2024-01-01"},
+              {"contributionCount": 6, "date": "2024-01-02"}
+            ]}
+          ]}
+        }}}
+      })
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  let options = FetchOptions {
+    include_private: true,
+    ..Default::default()
+  };
+  let fetcher = GitHubFetcher::new(&server.url(), Some("test-token"))
+    .unwrap()
+    .with_options(options.clone());
+  let stats = fetcher.fetch_user_stats("octocat", None).await.unwrap();
+
+  viewer.assert_async().await;
+  assert_eq!(stats.total_contributions, 10);
+  assert_eq!(stats.private_contributions, Some(5));
+
+  // Someone else's calendar stays public
+  server
+    .mock("GET", "/api/v3/users/hubot/repos")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body("[]")
+    .create_async()
+    .await;
+  let fetcher = GitHubFetcher::new(&server.url(), Some("test-token"))
+    .unwrap()
+    .with_options(options);
+  let stats = fetcher.fetch_user_stats("hubot", None).await.unwrap();
+  assert_eq!(stats.private_contributions, None);
+}
@@ -0,0 +1,208 @@
+use chrono::{Duration, Utc};
+use gitfetch_rs::fetcher::gitlab::GitLabFetcher;
+use gitfetch_rs::fetcher::Fetcher;
+use gitfetch_rs::models::UserData;
+use mockito::Matcher;
+use serde_json::json;
+
+fn days_ago(days: i64) -> String {
+  (Utc::now().date_naive() - Duration::days(days))
+    .format("%Y-%m-%d")
+    .to_string()
+}
+
+async fn mock_projects(server: &mut mockito::Server) {
+  server
+    .mock("GET", "/api/v4/users/7/projects")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(json!([{"star_count": 4, "forks_count": 1}]).to_string())
+    .create_async()
+    .await;
+}
+
+#[tokio::test]
+async fn test_contribution_graph_from_calendar() {
+  let mut server = mockito::Server::new_async().await;
+  mock_projects(&mut server).await;
+
+  server
+    .mock("GET", "/users/alice/calendar.json")
+    .with_status(200)
+    .with_body(json!({ days_ago(0): 2, days_ago(1): 1, days_ago(5): 4 }).to_string())
+    .create_async()
+    .await;
+
+  let fetcher = GitLabFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let user = UserData {
+    login: "alice".to_string(),
+    id: Some(7),
+    ..Default::default()
+  };
+  let stats = fetcher
+    .fetch_user_stats("alice", Some(&user))
+    .await
+    .unwrap();
+
+  let weeks = &stats.contribution_graph;
+  assert!(weeks.len() >= 52);
+  assert_eq!(weeks[0].contribution_days.len(), 7);
+
+  assert_eq!(stats.total_contributions, 7);
+  assert_eq!(stats.current_streak, 2);
+  assert_eq!(stats.longest_streak, 2);
+  assert_eq!(stats.total_stars, 4);
+}
+
+#[tokio::test]
+async fn test_contribution_graph_falls_back_to_events() {
+  let mut server = mockito::Server::new_async().await;
+  mock_projects(&mut server).await;
+
+  server
+    .mock("GET", "/users/alice/calendar.json")
+    .with_status(404)
+    .create_async()
+    .await;
+
+  let today = format!("{}T10:00:00.000Z", days_ago(0));
+  server
+    .mock("GET", "/api/v4/users/7/events")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(json!([{"created_at": today}, {"created_at": today}]).to_string())
+    .create_async()
+    .await;
+
+  let fetcher = GitLabFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let user = UserData {
+    login: "alice".to_string(),
+    id: Some(7),
+    ..Default::default()
+  };
+  let stats = fetcher
+    .fetch_user_stats("alice", Some(&user))
+    .await
+    .unwrap();
+
+  assert_eq!(stats.total_contributions, 2);
+  assert_eq!(stats.current_streak, 1);
+}
+
+#[tokio::test]
+async fn test_merge_request_and_issue_stats() {
+  let mut server = mockito::Server::new_async().await;
+  mock_projects(&mut server).await;
+
+  server
+    .mock("GET", "/users/alice/calendar.json")
+    .with_status(200)
+    .with_body("{}")
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/api/v4/user")
+    .with_status(200)
+    .with_body(json!({"id": 7, "username": "alice"}).to_string())
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/api/v4/merge_requests")
+    .match_query(Matcher::UrlEncoded("reviewer_id".into(), "7".into()))
+    .with_status(200)
+    .with_header("x-total", "12")
+    .with_body(
+      json!([{
+        "title": "Add calendar support",
+        "iid": 3,
+        "web_url": "https://gitlab.example/group/app/-/merge_requests/3",
+        "references": {"full": "group/app!3"}
+      }])
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/api/v4/merge_requests")
+    .match_query(Matcher::UrlEncoded("author_id".into(), "7".into()))
+    .with_status(200)
+    .with_body("[]")
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/api/v4/issues")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body("[]")
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/api/v4/todos")
+    .match_query(Matcher::UrlEncoded("type".into(), "Issue".into()))
+    .with_status(200)
+    .with_body(
+      json!([{
+        "target": {
+          "title": "Crash on startup",
+          "iid": 9,
+          "web_url": "https://gitlab.example/group/app/-/issues/9",
+          "references": {"full": "group/app#9"}
+        }
+      }])
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  server
+    .mock("GET", "/api/v4/todos")
+    .match_query(Matcher::UrlEncoded("type".into(), "MergeRequest".into()))
+    .with_status(200)
+    .with_body("[]")
+    .create_async()
+    .await;
+
+  let fetcher = GitLabFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let user = UserData {
+    login: "alice".to_string(),
+    id: Some(7),
+    ..Default::default()
+  };
+  let stats = fetcher
+    .fetch_user_stats("alice", Some(&user))
+    .await
+    .unwrap();
+
+  let awaiting = &stats.pull_requests.awaiting_review;
+  assert_eq!(awaiting.total_count, 12);
+  assert_eq!(awaiting.items[0].repo, "group/app");
+  assert_eq!(awaiting.items[0].number, 3);
+  assert_eq!(stats.pull_requests.open.total_count, 0);
+
+  let mentions = &stats.issues.mentions;
+  assert_eq!(mentions.total_count, 1);
+  assert_eq!(mentions.items[0].title, "Crash on startup");
+}
+
+#[tokio::test]
+async fn test_fetch_subgroup_members() {
+  let mut server = mockito::Server::new_async().await;
+  let mock = server
+    .mock("GET", "/api/v4/groups/acme%2Fplatform/members/all")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(json!([{"username": "alice"}, {"username": "bob"}]).to_string())
+    .create_async()
+    .await;
+
+  let fetcher = GitLabFetcher::new(&server.url(), None).unwrap();
+  let members = fetcher.fetch_org_members("acme/platform").await.unwrap();
+
+  assert_eq!(members, vec!["alice", "bob"]);
+  mock.assert_async().await;
+}
@@ -0,0 +1,167 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use gitfetch_rs::config::Config;
+use gitfetch_rs::display::ansi;
+use gitfetch_rs::display::formatter::{DisplayFormatter, VisualOptions};
+use gitfetch_rs::display::graph::ContributionGraph;
+use gitfetch_rs::fetcher::gitlab::GitLabFetcher;
+use gitfetch_rs::fetcher::Fetcher;
+use gitfetch_rs::models::{ContributionDay, ContributionWeek, UserData};
+use gitfetch_rs::utils::calendar::{self, DateRange, YearCalendar};
+use mockito::Matcher;
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Calendar for `year` with the given `(month, day, count)` entries
+fn year(year: i32, entries: &[(u32, u32, u32)]) -> YearCalendar {
+  let counts: HashMap<NaiveDate, u32> = entries
+    .iter()
+    .map(|&(m, d, count)| (NaiveDate::from_ymd_opt(year, m, d).unwrap(), count))
+    .collect();
+  let range = DateRange::year(year).unwrap();
+  YearCalendar {
+    year,
+    weeks: calendar::weeks_from_daily_counts(&counts, range.start, range.end),
+  }
+}
+
+#[test]
+fn test_history_summary() {
+  let years = [
+    year(2022, &[(3, 1, 4), (3, 2, 4), (7, 9, 1)]),
+    year(2023, &[(1, 1, 2), (5, 20, 6), (11, 3, 1)]),
+    year(2024, &[]),
+  ];
+
+  assert_eq!(years[0].total(), 9);
+  assert_eq!(years[0].longest_streak(), 2);
+
+  let summary = calendar::history_summary(&years);
+  assert_eq!(summary.total, 18);
+  assert_eq!(summary.best_year, Some((2022, 9)));
+  assert_eq!(
+    summary.best_month,
+    Some((NaiveDate::from_ymd_opt(2022, 3, 1).unwrap(), 8))
+  );
+
+  assert_eq!(
+    calendar::history_summary(&years[2..]),
+    calendar::HistorySummary::default()
+  );
+}
+
+#[test]
+fn test_year_streak_stays_within_the_year() {
+  // Providers may hand back the previous December in the first week
+  let days = [
+    ("2021-12-30", 1),
+    ("2021-12-31", 1),
+    ("2022-01-01", 1),
+    ("2022-01-02", 0),
+    ("2022-01-03", 2),
+    ("2022-01-04", 1),
+  ];
+  let calendar = YearCalendar {
+    year: 2022,
+    weeks: vec![ContributionWeek {
+      contribution_days: days
+        .iter()
+        .map(|&(date, count)| ContributionDay {
+          contribution_count: count,
+          date: date.to_string(),
+        })
+        .collect(),
+    }],
+  };
+
+  assert_eq!(calendar.total(), 4);
+  assert_eq!(calendar.longest_streak(), 2);
+}
+
+#[test]
+fn test_history_years() {
+  let current = Utc::now().year();
+  assert_eq!(
+    calendar::history_years(Some("2019-06-01T12:00:00Z")),
+    2019..=current
+  );
+  assert_eq!(calendar::history_years(None), current..=current);
+  assert_eq!(
+    calendar::history_years(Some("not a date")),
+    current..=current
+  );
+}
+
+#[test]
+fn test_history_lines() {
+  let years = [
+    year(2022, &[(3, 1, 4), (3, 2, 4)]),
+    year(2023, &[(5, 20, 6)]),
+  ];
+  let formatter = DisplayFormatter::new(Config::default(), VisualOptions::default()).unwrap();
+  let lines: Vec<String> = formatter
+    .history_lines("Octo", &years)
+    .iter()
+    .map(|line| {
+      ansi::parse(line)
+        .into_iter()
+        .map(|span| span.text)
+        .collect()
+    })
+    .collect();
+
+  assert!(lines.contains(&"    2022 - 8 contributions, longest streak 2d".to_string()));
+  assert!(lines.contains(&"    2023 - 6 contributions, longest streak 1d".to_string()));
+  assert!(lines.contains(&"    Octo - all time".to_string()));
+  assert!(lines
+    .iter()
+    .any(|line| line.trim_end() == "    Total:       14"));
+  assert!(lines
+    .iter()
+    .any(|line| line.trim_end() == "    Best year:   2022 (8)"));
+  assert!(lines
+    .iter()
+    .any(|line| line.trim_end() == "    Best month:  March 2022 (8)"));
+}
+
+#[tokio::test]
+async fn test_gitlab_past_year_calendar_uses_events() {
+  let mut server = mockito::Server::new_async().await;
+
+  let events = server
+    .mock("GET", "/api/v4/users/7/events")
+    .match_query(Matcher::AllOf(vec![
+      Matcher::UrlEncoded("after".into(), "2019-12-31".into()),
+      Matcher::UrlEncoded("before".into(), "2021-01-01".into()),
+    ]))
+    .with_status(200)
+    .with_body(
+      json!([
+        {"created_at": "2020-04-02T10:00:00.000Z"},
+        {"created_at": "2020-04-02T11:00:00.000Z"}
+      ])
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  let fetcher = GitLabFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let user = UserData {
+    login: "alice".to_string(),
+    id: Some(7),
+    ..Default::default()
+  };
+  let weeks: Vec<ContributionWeek> = fetcher
+    .fetch_contribution_calendar(&user, DateRange::year(2020).unwrap())
+    .await
+    .unwrap();
+
+  events.assert_async().await;
+  assert_eq!(
+    ContributionGraph::from_weeks(&weeks).calculate_total_contributions(),
+    2
+  );
+  assert_eq!(
+    weeks.last().unwrap().contribution_days.last().unwrap().date,
+    "2020-12-31"
+  );
+}
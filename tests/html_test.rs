@@ -0,0 +1,117 @@
+use gitfetch_rs::config::Config;
+use gitfetch_rs::display::formatter::{DisplayFormatter, VisualOptions};
+use gitfetch_rs::models::{LineChanges, UserStats};
+use gitfetch_rs::utils::calendar::DateRange;
+
+mod common;
+
+#[test]
+fn test_render_html_report() {
+  let formatter = DisplayFormatter::new(Config::default(), VisualOptions::default()).unwrap();
+  let user = common::sample_user();
+  let stats = common::sample_stats();
+  let html = formatter.render_html("octocat", &user, &stats);
+
+  assert!(html.starts_with("<!DOCTYPE html>"));
+  assert!(html.trim_end().ends_with("</html>"));
+  assert!(html.contains("<h1>Octo &lt;Cat&gt;</h1>"));
+  assert!(html.contains("<a href=\"https://octo.example\">"));
+
+  assert!(html.contains("title=\"5 contributions on 2024-02-04\""));
+  assert!(html.contains("title=\"1 contribution on 2024-01-29\""));
+  assert!(html.contains("title=\"No contributions on 2024-01-28\""));
+}
+
+#[test]
+fn test_render_html_pull_requests_collapsible() {
+  let formatter = DisplayFormatter::new(Config::default(), VisualOptions::default()).unwrap();
+  let user = common::sample_user();
+  let stats = common::sample_stats();
+  let html = formatter.render_html("octocat", &user, &stats);
+
+  assert!(html.contains("<summary>Your Open PRs (2)</summary>"));
+  assert!(html.contains(
+    "<a href=\"https://github.com/octo/repo/pull/7\">Fix &lt;script&gt; &amp; escape</a>"
+  ));
+  assert!(!html.contains("<script>"));
+}
+
+#[test]
+fn test_render_html_respects_hidden_sections() {
+  let visual_opts = VisualOptions {
+    no_grid: true,
+    no_pr: true,
+    ..Default::default()
+  };
+  let formatter = DisplayFormatter::new(Config::default(), visual_opts).unwrap();
+  let user = common::sample_user();
+  let stats = common::sample_stats();
+  let html = formatter.render_html("octocat", &user, &stats);
+
+  assert!(!html.contains("class=\"calendar\""));
+  assert!(!html.contains("Pull Requests"));
+  assert!(html.contains("<h2>Issues</h2>"));
+}
+
+#[test]
+fn test_render_html_tooltips_include_line_changes() {
+  let formatter = DisplayFormatter::new(Config::default(), VisualOptions::default()).unwrap();
+  let user = common::sample_user();
+  let mut stats = common::sample_stats();
+  stats.line_changes.insert(
+    "2024-02-04".to_string(),
+    LineChanges {
+      files_changed: 3,
+      additions: 40,
+      deletions: 2,
+    },
+  );
+  let html = formatter.render_html("octocat", &user, &stats);
+
+  assert!(html.contains("title=\"5 contributions on 2024-02-04 (3 files, +40 -2)\""));
+  assert!(html.contains("title=\"1 contribution on 2024-01-29\""));
+}
+
+#[test]
+fn test_render_html_header_names_the_range() {
+  let user = common::sample_user();
+  let stats = common::sample_stats();
+  let html = DisplayFormatter::new(Config::default(), VisualOptions::default())
+    .unwrap()
+    .render_html("octocat", &user, &stats);
+  assert!(html.contains("</strong> contributions this year</p>"));
+
+  let visual_opts = VisualOptions {
+    range: Some(DateRange::year(2024).unwrap()),
+    ..Default::default()
+  };
+  let html = DisplayFormatter::new(Config::default(), visual_opts)
+    .unwrap()
+    .render_html("octocat", &user, &stats);
+  assert!(html.contains("</strong> contributions in 2024</p>"));
+
+  let stats = UserStats {
+    private_contributions: Some(3),
+    ..stats
+  };
+  let html = DisplayFormatter::new(Config::default(), VisualOptions::default())
+    .unwrap()
+    .render_html("octocat", &user, &stats);
+  assert!(html.contains("</strong> contributions this year (incl. private)</p>"));
+}
+
+#[test]
+fn test_render_html_only_links_http_urls() {
+  let mut user = common::sample_user();
+  let mut stats = common::sample_stats();
+  user.blog = Some("javascript:alert(1)".to_string());
+  stats.pull_requests.open.items[0].url = " JavaScript:alert(2)".to_string();
+  let html = DisplayFormatter::new(Config::default(), VisualOptions::default())
+    .unwrap()
+    .render_html("octocat", &user, &stats);
+
+  assert!(!html.to_lowercase().contains("href=\"javascript:"));
+  assert!(!html.to_lowercase().contains("href=\" javascript:"));
+  assert!(html.contains("<dt>Website</dt><dd>javascript:alert(1)</dd>"));
+  assert!(html.contains("<li>Fix &lt;script&gt; &amp; escape <span class=\"repo\">"));
+}
@@ -50,7 +50,7 @@ fn test_text_to_grid_with_space() {
 
 #[test]
 fn test_shape_to_grid_heart() {
-  let result = shape_to_grid(&vec!["heart".to_string()]);
+  let result = shape_to_grid(&["heart".to_string()]);
   assert!(result.is_ok());
 
   let grid = result.unwrap();
@@ -60,7 +60,7 @@ fn test_shape_to_grid_heart() {
 
 #[test]
 fn test_shape_to_grid_octocat() {
-  let result = shape_to_grid(&vec!["octocat".to_string()]);
+  let result = shape_to_grid(&["octocat".to_string()]);
   assert!(result.is_ok());
 
   let grid = result.unwrap();
@@ -69,7 +69,7 @@ fn test_shape_to_grid_octocat() {
 
 #[test]
 fn test_shape_to_grid_unknown() {
-  let result = shape_to_grid(&vec!["unknown_shape".to_string()]);
+  let result = shape_to_grid(&["unknown_shape".to_string()]);
   assert!(result.is_err());
 
   let err = result.unwrap_err();
@@ -78,7 +78,7 @@ fn test_shape_to_grid_unknown() {
 
 #[test]
 fn test_shape_to_grid_multiple() {
-  let result = shape_to_grid(&vec!["heart".to_string(), "octocat".to_string()]);
+  let result = shape_to_grid(&["heart".to_string(), "octocat".to_string()]);
   assert!(result.is_ok());
 
   let grid = result.unwrap();
@@ -98,7 +98,7 @@ fn test_text_to_grid_empty() {
 
 #[test]
 fn test_shape_to_grid_empty() {
-  let result = shape_to_grid(&vec![]);
+  let result = shape_to_grid(&[]);
   assert!(result.is_ok());
 
   let grid = result.unwrap();
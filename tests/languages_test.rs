@@ -0,0 +1,110 @@
+use gitfetch_rs::config::LanguageWeighting;
+use gitfetch_rs::utils::languages::{
+  by_primary_language, from_breakdowns, language_for_path, language_stats,
+};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn test_from_breakdowns_sums_weighted_amounts() {
+  let small = json!({"Rust": 100, "Shell": 100});
+  let large = json!({"Rust": 700, "Go": 100});
+
+  let languages = from_breakdowns([(&small, 1.0), (&large, 1.0)]);
+  assert_eq!(languages["Rust"], 80.0);
+  assert_eq!(languages["Shell"], 10.0);
+  assert_eq!(languages["Go"], 10.0);
+
+  // Percentage breakdowns scaled by repository size
+  let a = json!({"Rust": 50.0, "C": 50.0});
+  let b = json!({"C": 100.0});
+  let languages = from_breakdowns([(&a, 3.0), (&b, 1.0)]);
+  assert_eq!(languages["Rust"], 37.5);
+  assert_eq!(languages["C"], 62.5);
+
+  assert!(from_breakdowns([(&json!({}), 1.0)]).is_empty());
+}
+
+#[test]
+fn test_by_primary_language_counts_repos() {
+  let repos = vec![
+    json!({"language": "Rust"}),
+    json!({"language": "rust"}),
+    json!({"language": "Go"}),
+    json!({"language": null}),
+  ];
+
+  let languages = by_primary_language(&repos);
+  assert_eq!(languages.len(), 2);
+  assert!((languages["Rust"] - 200.0 / 3.0).abs() < 1e-9);
+  assert!((languages["Go"] - 100.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_by_primary_language_keeps_provider_spelling() {
+  let repos = vec![
+    json!({"language": "JavaScript"}),
+    json!({"language": "javascript"}),
+    json!({"language": "CSS"}),
+    json!({"language": "css"}),
+    json!({"language": "Elisp"}),
+  ];
+
+  let languages = by_primary_language(&repos);
+  assert_eq!(languages.len(), 3);
+  assert_eq!(languages["JavaScript"], 40.0);
+  assert_eq!(languages["CSS"], 40.0);
+  assert_eq!(languages["Elisp"], 20.0);
+}
+
+#[tokio::test]
+async fn test_language_stats_weighting() {
+  let repos = vec![json!({"language": "Go"}), json!({"language": "Rust"})];
+  let requests = vec![("/a".to_string(), 1.0), ("/b".to_string(), 1.0)];
+  let calls = AtomicUsize::new(0);
+  let fetch = |endpoint: String| {
+    calls.fetch_add(1, Ordering::SeqCst);
+    async move {
+      match endpoint.as_str() {
+        "/a" => Ok(json!({"Go": 100})),
+        _ => Ok(json!({"Rust": 900})),
+      }
+    }
+  };
+
+  let bytes = language_stats(&repos, LanguageWeighting::Bytes, requests.clone(), fetch).await;
+  assert_eq!(bytes["Rust"], 900.0);
+  assert_eq!(bytes["Go"], 100.0);
+  assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+  let counted = language_stats(&repos, LanguageWeighting::Repos, requests, fetch).await;
+  assert_eq!(counted["Rust"], 1.0);
+  assert_eq!(counted["Go"], 1.0);
+  assert_eq!(
+    calls.load(Ordering::SeqCst),
+    2,
+    "repo weighting makes no requests"
+  );
+}
+
+#[tokio::test]
+async fn test_language_stats_falls_back_when_requests_fail() {
+  let repos = vec![json!({"language": "Go"})];
+  let requests = vec![("/a".to_string(), 1.0)];
+
+  let languages = language_stats(&repos, LanguageWeighting::Bytes, requests, |_| async {
+    Err::<Value, _>(anyhow::anyhow!("rate limited"))
+  })
+  .await;
+
+  assert_eq!(languages["Go"], 1.0);
+}
+
+#[test]
+fn test_language_for_path() {
+  assert_eq!(language_for_path("src/main.rs"), Some("Rust"));
+  assert_eq!(language_for_path("web/App.TSX"), Some("TypeScript"));
+  assert_eq!(language_for_path("docker/Dockerfile"), Some("Dockerfile"));
+  assert_eq!(language_for_path("README.md"), None);
+  assert_eq!(language_for_path("LICENSE"), None);
+}
@@ -0,0 +1,84 @@
+use gitfetch_rs::config::Config;
+use gitfetch_rs::display::linguist::{language_color, segment_widths, DEFAULT_COLOR};
+use gitfetch_rs::display::{DisplayFormatter, VisualOptions};
+use gitfetch_rs::models::{RepoStats, UserData, UserStats};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+fn config_with_override() -> Config {
+  let mut config = Config::default();
+  config
+    .language_colors
+    .insert("rust".to_string(), "123456".to_string());
+  config
+}
+
+fn sample_stats() -> UserStats {
+  serde_json::from_value(json!({
+    "languages": {"Rust": 60.0, "Go": 30.0, "Brainfuck": 10.0},
+    "contribution_graph": []
+  }))
+  .unwrap()
+}
+
+#[test]
+fn test_language_color_lookup_and_overrides() {
+  let none = BTreeMap::new();
+  assert_eq!(language_color("Rust", &none), "#dea584");
+  // Repo-count stats spell names "Javascript"
+  assert_eq!(language_color("Javascript", &none), "#f1e05a");
+  assert_eq!(language_color("Unheard-of", &none), DEFAULT_COLOR);
+
+  let overrides = config_with_override().language_colors;
+  assert_eq!(language_color("Rust", &overrides), "#123456");
+
+  let invalid = BTreeMap::from([("Go".to_string(), "blue".to_string())]);
+  assert_eq!(language_color("Go", &invalid), "#00ADD8");
+}
+
+#[test]
+fn test_segment_widths_fill_the_bar() {
+  assert_eq!(segment_widths(&[50.0, 50.0], 10), vec![5, 5]);
+  assert_eq!(segment_widths(&[33.3, 33.3, 33.4], 10), vec![3, 3, 4]);
+  assert_eq!(segment_widths(&[99.0, 1.0], 44).iter().sum::<usize>(), 44);
+  assert_eq!(segment_widths(&[], 10), Vec::<usize>::new());
+}
+
+#[test]
+fn test_language_bars_use_language_colors() {
+  let formatter = DisplayFormatter::new(config_with_override(), VisualOptions::default()).unwrap();
+  let svg = formatter.render_svg("octocat", &UserData::default(), &sample_stats());
+
+  assert!(svg.contains("fill=\"#123456\""));
+  assert!(svg.contains("fill=\"#00ADD8\""));
+  assert!(svg.contains("fill=\"#50fa7b\""));
+}
+
+#[test]
+fn test_stacked_language_bar() {
+  let visual_opts = VisualOptions {
+    language_bar: true,
+    ..Default::default()
+  };
+  let formatter = DisplayFormatter::new(Config::default(), visual_opts).unwrap();
+
+  let repo: RepoStats = serde_json::from_value(json!({
+    "full_name": "octo/hello",
+    "languages": {"Rust": 75.0, "Go": 25.0}
+  }))
+  .unwrap();
+  let lines = formatter.repo_lines(&repo);
+  let bar = lines
+    .iter()
+    .find(|line| line.contains("\x1b[38;2;222;165;132m▰"))
+    .expect("stacked bar line");
+  assert_eq!(bar.matches('▰').count(), 44);
+  assert!(lines
+    .iter()
+    .any(|line| line.contains("Rust 75.0%") && line.contains("Go 25.0%")));
+
+  let html = formatter.render_html("octocat", &UserData::default(), &sample_stats());
+  assert!(html.contains("<div class=\"strip\">"));
+  assert!(html.contains("width: 60.0%; background: #dea584"));
+  assert!(!html.contains("Other"));
+}
@@ -0,0 +1,257 @@
+use chrono::{Duration, Utc};
+use git2::{Repository, Signature, Time};
+use gitfetch_rs::config::Config;
+use gitfetch_rs::display::{DisplayFormatter, VisualOptions};
+use gitfetch_rs::utils::calendar::DateRange;
+use gitfetch_rs::utils::git::{analyze_repo, analyze_workspace, find_repositories, LocalOptions};
+use std::path::Path;
+
+/// Commits `files` on top of HEAD as `email`, `days_ago` days back
+fn commit(repo: &Repository, email: &str, days_ago: i64, files: &[(&str, &str)]) {
+  commit_as(repo, "Dev", email, days_ago, files);
+}
+
+fn commit_as(repo: &Repository, name: &str, email: &str, days_ago: i64, files: &[(&str, &str)]) {
+  let workdir = repo.workdir().unwrap();
+  let mut index = repo.index().unwrap();
+  for (path, content) in files {
+    let full = workdir.join(path);
+    std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+    std::fs::write(&full, content).unwrap();
+    index.add_path(Path::new(path)).unwrap();
+  }
+  index.write().unwrap();
+  let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+
+  let when = Utc::now() - Duration::days(days_ago);
+  let signature = Signature::new(name, email, &Time::new(when.timestamp(), 0)).unwrap();
+  let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+  let parents: Vec<_> = parent.iter().collect();
+  repo
+    .commit(
+      Some("HEAD"),
+      &signature,
+      &signature,
+      "change",
+      &tree,
+      &parents,
+    )
+    .unwrap();
+}
+
+fn init(path: &Path) -> Repository {
+  let repo = Repository::init(path).unwrap();
+  {
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Dev").unwrap();
+    config.set_str("user.email", "dev@example.com").unwrap();
+  }
+  repo
+}
+
+fn sample_repo() -> (tempfile::TempDir, Repository) {
+  let dir = tempfile::tempdir().unwrap();
+  let repo = init(dir.path());
+
+  commit(
+    &repo,
+    "dev@example.com",
+    2,
+    &[("src/main.rs", "fn main() {}\n// two\n")],
+  );
+  commit(&repo, "DEV@example.com", 1, &[("build.sh", "echo hi\n")]);
+  commit(
+    &repo,
+    "other@example.com",
+    1,
+    &[("src/lib.rs", "pub fn x() {}\n")],
+  );
+  commit(
+    &repo,
+    "dev@example.com",
+    0,
+    &[("src/main.rs", "fn main() {}\n")],
+  );
+  commit(
+    &repo,
+    "other@example.com",
+    0,
+    &[("vendor/big.js", &"x".repeat(10_000))],
+  );
+
+  (dir, repo)
+}
+
+fn day(days_ago: i64) -> String {
+  (Utc::now() - Duration::days(days_ago))
+    .format("%Y-%m-%d")
+    .to_string()
+}
+
+#[test]
+fn test_analyze_repo_filters_by_user_email() {
+  let (dir, _repo) = sample_repo();
+  let (user, stats) = analyze_repo(dir.path(), &LocalOptions::default()).unwrap();
+
+  assert_eq!(user.email.as_deref(), Some("dev@example.com"));
+  assert_eq!(stats.total_contributions, 3);
+  assert_eq!(stats.current_streak, 3);
+  assert_eq!(stats.longest_streak, 3);
+
+  let first = &stats.line_changes[&day(2)];
+  assert_eq!(
+    (first.files_changed, first.additions, first.deletions),
+    (1, 2, 0)
+  );
+  let last = &stats.line_changes[&day(0)];
+  assert_eq!(
+    (last.files_changed, last.additions, last.deletions),
+    (1, 0, 1)
+  );
+}
+
+#[test]
+fn test_analyze_repo_all_authors() {
+  let (dir, _repo) = sample_repo();
+  let options = LocalOptions {
+    all_authors: true,
+    ..Default::default()
+  };
+  let (_, stats) = analyze_repo(dir.path(), &options).unwrap();
+
+  assert_eq!(stats.total_contributions, 5);
+  assert_eq!(stats.line_changes[&day(1)].files_changed, 2);
+}
+
+#[test]
+fn test_analyze_repo_date_range() {
+  let (dir, _repo) = sample_repo();
+  let yesterday = (Utc::now() - Duration::days(1)).date_naive();
+  let options = LocalOptions {
+    range: Some(DateRange::new(yesterday - Duration::days(6), yesterday).unwrap()),
+    ..Default::default()
+  };
+  let (_, stats) = analyze_repo(dir.path(), &options).unwrap();
+
+  assert_eq!(stats.total_contributions, 2);
+  assert_eq!(stats.current_streak, 2);
+  assert!(!stats.line_changes.contains_key(&day(0)));
+  let days: usize = stats
+    .contribution_graph
+    .iter()
+    .map(|week| week.contribution_days.len())
+    .sum();
+  assert!(days >= 7);
+  let last = stats.contribution_graph.last().unwrap();
+  assert_eq!(last.contribution_days.last().unwrap().date, day(1));
+}
+
+#[test]
+fn test_analyze_repo_languages_from_tracked_files() {
+  let (dir, _repo) = sample_repo();
+  let (_, stats) = analyze_repo(dir.path(), &LocalOptions::default()).unwrap();
+
+  // main.rs (13 bytes) + lib.rs (14) vs build.sh (8); vendor/ is skipped
+  assert_eq!(stats.languages.len(), 2);
+  assert!((stats.languages["Rust"] - 27.0 / 35.0 * 100.0).abs() < 1e-9);
+  assert!((stats.languages["Shell"] - 8.0 / 35.0 * 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_analyze_workspace_merges_repositories() {
+  let root = tempfile::tempdir().unwrap();
+
+  let api = init(&root.path().join("work/api"));
+  commit(&api, "dev@example.com", 1, &[("main.go", "package main\n")]);
+  commit(&api, "dev@example.com", 0, &[("util.go", "package main\n")]);
+
+  let site = init(&root.path().join("site"));
+  commit(&site, "dev@example.com", 0, &[("index.ts", "export {}\n")]);
+
+  let idle = init(&root.path().join("idle"));
+  commit(&idle, "other@example.com", 0, &[("x.py", "pass\n")]);
+
+  // Hidden directories and nested repositories aren't scanned
+  let hidden = init(&root.path().join(".cache/repo"));
+  commit(&hidden, "dev@example.com", 0, &[("a.rs", "\n")]);
+  let nested = init(&root.path().join("site/vendor-repo"));
+  commit(&nested, "dev@example.com", 0, &[("b.rs", "\n")]);
+
+  let repos = find_repositories(root.path()).unwrap();
+  assert_eq!(repos.len(), 3);
+
+  let workspace = analyze_workspace(root.path(), &LocalOptions::default()).unwrap();
+  let stats = workspace.stats;
+  assert!(workspace.user_data.bio.unwrap().contains("3 repositories"));
+  assert_eq!(stats.total_repos, 3);
+  assert_eq!(stats.total_contributions, 3);
+  assert_eq!(stats.current_streak, 2);
+  assert_eq!(stats.line_changes[&day(0)].files_changed, 2);
+  assert_eq!(
+    workspace.repos,
+    vec![
+      ("work/api".to_string(), 2),
+      ("site".to_string(), 1),
+      ("idle".to_string(), 0),
+    ]
+  );
+
+  // Languages are weighted by commits, so the idle Python repo drops out
+  assert!(!stats.languages.contains_key("Python"));
+  assert!(stats.languages["Go"] > stats.languages["TypeScript"]);
+}
+
+#[test]
+fn test_repo_commit_lines() {
+  let formatter = DisplayFormatter::new(Config::default(), VisualOptions::default()).unwrap();
+  let repos = vec![
+    ("work/api".to_string(), 12),
+    ("site".to_string(), 3),
+    ("old".to_string(), 0),
+    ("older".to_string(), 0),
+  ];
+  let lines: Vec<String> = formatter
+    .repo_commit_lines(&repos)
+    .iter()
+    .map(|line| {
+      regex::Regex::new(r"\x1b\[[0-9;]*m")
+        .unwrap()
+        .replace_all(line, "")
+        .to_string()
+    })
+    .collect();
+
+  assert_eq!(lines[0], "REPOSITORIES");
+  assert_eq!(lines[3], "     12  work/api");
+  assert_eq!(lines[4], "      3  site");
+  assert_eq!(lines[5], "2 more repositories without commits");
+  assert_eq!(lines.len(), 6);
+}
+
+#[test]
+fn test_analyze_repo_mailmap_and_aliases() {
+  let dir = tempfile::tempdir().unwrap();
+  let repo = init(dir.path());
+  commit(
+    &repo,
+    "dev@example.com",
+    3,
+    &[(".mailmap", "Dev <dev@example.com> <dev@laptop.local>\n")],
+  );
+  commit(&repo, "dev@laptop.local", 2, &[("a.rs", "a\n")]);
+  commit(&repo, "dev@corp.example", 1, &[("b.rs", "b\n")]);
+  commit_as(&repo, "Dev Bot", "bot@ci.example", 1, &[("c.rs", "c\n")]);
+  commit(&repo, "stranger@example.com", 0, &[("d.rs", "d\n")]);
+
+  // .mailmap alone folds the laptop address into user.email
+  let (_, stats) = analyze_repo(dir.path(), &LocalOptions::default()).unwrap();
+  assert_eq!(stats.total_contributions, 2);
+
+  let options = LocalOptions {
+    aliases: vec!["DEV@corp.example".to_string(), "dev bot".to_string()],
+    ..Default::default()
+  };
+  let (_, stats) = analyze_repo(dir.path(), &options).unwrap();
+  assert_eq!(stats.total_contributions, 4);
+  assert!(!stats.line_changes.contains_key(&day(0)));
+}
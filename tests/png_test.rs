@@ -0,0 +1,54 @@
+use gitfetch_rs::display::raster::render_png;
+
+fn decode(bytes: &[u8]) -> (u32, u32, Vec<u8>) {
+  let decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+  let mut reader = decoder.read_info().unwrap();
+  let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+  let info = reader.next_frame(&mut pixels).unwrap();
+  (info.width, info.height, pixels)
+}
+
+fn pixel(pixels: &[u8], width: u32, x: u32, y: u32) -> (u8, u8, u8) {
+  let idx = ((y * width + x) * 3) as usize;
+  (pixels[idx], pixels[idx + 1], pixels[idx + 2])
+}
+
+#[test]
+fn test_background_blocks_become_pixels() {
+  // Two not-spaced contribution blocks, as emitted by get_contribution_block
+  let lines = vec![
+    "\x1b[48;2;255;0;0m  \x1b[0m\x1b[48;2;0;0;255m  \x1b[0m".to_string(),
+    String::new(),
+  ];
+  let (width, height, pixels) = decode(&render_png(&lines).unwrap());
+
+  // 4 cells wide, 2 lines tall, plus padding on every side
+  assert!(width > 4 * 8 && width < 4 * 12 + 40);
+  assert!(height > 2 * 16);
+
+  assert_eq!(pixel(&pixels, width, 18, 20), (255, 0, 0));
+  assert_eq!(pixel(&pixels, width, width - 18, 20), (0, 0, 255));
+  assert_eq!(pixel(&pixels, width, 1, 1), (13, 17, 23));
+}
+
+#[test]
+fn test_foreground_glyphs_are_drawn() {
+  let lines = vec!["\x1b[38;2;80;250;123m▰▰▰▰\x1b[0m─".to_string()];
+  let (width, _, pixels) = decode(&render_png(&lines).unwrap());
+
+  let green = pixels
+    .chunks(3)
+    .filter(|p| p[1] > 200 && p[0] < 120)
+    .count();
+  assert!(green > 20, "progress bar glyphs should be rendered");
+  assert!(width > 5 * 8);
+}
+
+#[test]
+fn test_emoji_fall_back_to_font_symbols() {
+  let lines = vec!["\x1b[38;2;80;250;123m⭐🔥\x1b[0m".to_string()];
+  let (_, _, pixels) = decode(&render_png(&lines).unwrap());
+
+  let drawn = pixels.chunks(3).filter(|p| p != &[13, 17, 23]).count();
+  assert!(drawn > 20, "emoji should not rasterize as blanks");
+}
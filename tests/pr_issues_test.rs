@@ -2,12 +2,20 @@
 mod pr_issues_tests {
   use gitfetch_rs::config::Config;
   use gitfetch_rs::display::formatter::{DisplayFormatter, VisualOptions};
-  use serde_json::json;
+  use gitfetch_rs::models::{UserData, UserStats};
+  use serde_json::{json, Value};
 
   fn create_test_config() -> Config {
     Config::default()
   }
 
+  // Goes through serde so the legacy `"open": 5` counter shape is exercised
+  fn display(formatter: &DisplayFormatter, user_data: Value, stats: Value) -> anyhow::Result<()> {
+    let user_data: UserData = serde_json::from_value(user_data).unwrap();
+    let stats: UserStats = serde_json::from_value(stats).unwrap();
+    formatter.display("testuser", &user_data, &stats)
+  }
+
   #[test]
   fn test_format_pull_requests_with_data() {
     let config = create_test_config();
@@ -29,7 +37,7 @@ mod pr_issues_tests {
     });
 
     // This should not panic even with PR data
-    let result = formatter.display("testuser", &user_data, &stats);
+    let result = display(&formatter, user_data, stats);
     assert!(result.is_ok());
   }
 
@@ -54,7 +62,7 @@ mod pr_issues_tests {
     });
 
     // This should not panic even with issue data
-    let result = formatter.display("testuser", &user_data, &stats);
+    let result = display(&formatter, user_data, stats);
     assert!(result.is_ok());
   }
 
@@ -83,7 +91,7 @@ mod pr_issues_tests {
     });
 
     // Should work with no_pr flag
-    let result = formatter.display("testuser", &user_data, &stats);
+    let result = display(&formatter, user_data, stats);
     assert!(result.is_ok());
   }
 
@@ -112,7 +120,7 @@ mod pr_issues_tests {
     });
 
     // Should work with no_issues flag
-    let result = formatter.display("testuser", &user_data, &stats);
+    let result = display(&formatter, user_data, stats);
     assert!(result.is_ok());
   }
 
@@ -137,7 +145,7 @@ mod pr_issues_tests {
     });
 
     // Should handle zero values gracefully
-    let result = formatter.display("testuser", &user_data, &stats);
+    let result = display(&formatter, user_data, stats);
     assert!(result.is_ok());
   }
 
@@ -167,7 +175,7 @@ mod pr_issues_tests {
     });
 
     // Should display both sections
-    let result = formatter.display("testuser", &user_data, &stats);
+    let result = display(&formatter, user_data, stats);
     assert!(result.is_ok());
   }
 }
@@ -0,0 +1,336 @@
+use chrono::{Duration, Utc};
+use gitfetch_rs::config::Config;
+use gitfetch_rs::display::ansi;
+use gitfetch_rs::display::export::RepoExportDocument;
+use gitfetch_rs::display::formatter::{DisplayFormatter, VisualOptions};
+use gitfetch_rs::display::OutputFormat;
+use gitfetch_rs::fetcher::gitea::GiteaFetcher;
+use gitfetch_rs::fetcher::github::GitHubFetcher;
+use gitfetch_rs::fetcher::repo::{split_repo, top_contributors};
+use gitfetch_rs::fetcher::{FetchOptions, Fetcher};
+use gitfetch_rs::models::{Contributor, RepoStats};
+use gitfetch_rs::utils::calendar::DateRange;
+use gitfetch_rs::utils::languages::from_breakdowns;
+use mockito::Matcher;
+use serde_json::json;
+
+fn plain(lines: &[String]) -> String {
+  lines
+    .iter()
+    .map(|line| {
+      ansi::parse(line)
+        .into_iter()
+        .map(|span| span.text)
+        .collect::<String>()
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+#[test]
+fn test_split_repo() {
+  assert_eq!(split_repo("octo/hello").unwrap(), ("octo", "hello"));
+  assert_eq!(
+    split_repo("group/sub/project").unwrap(),
+    ("group/sub", "project")
+  );
+  assert!(split_repo("hello").is_err());
+  assert!(split_repo("octo/").is_err());
+}
+
+#[test]
+fn test_byte_percentages_and_contributors() {
+  let languages = from_breakdowns([(&json!({"Rust": 3000, "Shell": 1000}), 1.0)]);
+  assert_eq!(languages["Rust"], 75.0);
+  assert_eq!(languages["Shell"], 25.0);
+
+  let authors = ["bob", "alice", "bob", "carol", "alice", "bob"].map(String::from);
+  assert_eq!(
+    top_contributors(authors)[..2],
+    [
+      Contributor {
+        login: "bob".to_string(),
+        contributions: 3
+      },
+      Contributor {
+        login: "alice".to_string(),
+        contributions: 2
+      },
+    ]
+  );
+}
+
+#[tokio::test]
+async fn test_github_repo_stats() {
+  let mut server = mockito::Server::new_async().await;
+  let last_sunday = {
+    let today = Utc::now().date_naive();
+    today - Duration::days(chrono::Datelike::weekday(&today).num_days_from_sunday() as i64)
+  };
+  let week = last_sunday
+    .and_hms_opt(0, 0, 0)
+    .unwrap()
+    .and_utc()
+    .timestamp();
+
+  server
+    .mock("GET", "/api/v3/repos/octo/hello")
+    .with_status(200)
+    .with_body(
+      json!({
+        "full_name": "octo/hello",
+        "description": "Hello world",
+        "html_url": "https://github.com/octo/hello",
+        "stargazers_count": 42,
+        "forks_count": 7
+      })
+      .to_string(),
+    )
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/api/v3/repos/octo/hello/languages")
+    .with_status(200)
+    .with_body(json!({"Rust": 900, "Python": 100}).to_string())
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/api/v3/repos/octo/hello/contributors")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(json!([{"login": "octocat", "contributions": 12}]).to_string())
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/api/v3/repos/octo/hello/stats/commit_activity")
+    .with_status(200)
+    .with_body(json!([{"week": week, "total": 3, "days": [2, 1, 0, 0, 0, 0, 0]}]).to_string())
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/api/v3/search/issues")
+    .match_query(Matcher::UrlEncoded(
+      "q".into(),
+      "repo:octo/hello is:pr state:open".into(),
+    ))
+    .with_status(200)
+    .with_body(
+      json!({"total_count": 1, "items": [{
+        "title": "Add greeting",
+        "repository_url": "https://api.github.com/repos/octo/hello",
+        "html_url": "https://github.com/octo/hello/pull/3",
+        "number": 3
+      }]})
+      .to_string(),
+    )
+    .create_async()
+    .await;
+
+  let fetcher = GitHubFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let repo = fetcher.fetch_repo_stats("octo/hello").await.unwrap();
+
+  assert_eq!(repo.full_name, "octo/hello");
+  assert_eq!(repo.stars, 42);
+  assert_eq!(repo.forks, 7);
+  assert_eq!(repo.languages["Rust"], 90.0);
+  assert_eq!(repo.contributors[0].login, "octocat");
+  assert_eq!(repo.pull_requests.total_count, 1);
+  assert_eq!(repo.pull_requests.items[0].number, 3);
+  assert_eq!(repo.issues.total_count, 0);
+
+  let days: Vec<_> = repo
+    .commit_activity
+    .iter()
+    .flat_map(|w| &w.contribution_days)
+    .filter(|d| d.contribution_count > 0)
+    .map(|d| d.contribution_count)
+    .collect();
+  assert!(!days.is_empty() && days[0] == 2);
+}
+
+#[tokio::test]
+async fn test_gitea_repo_stats_from_commits() {
+  let mut server = mockito::Server::new_async().await;
+  let yesterday = (Utc::now() - Duration::days(1)).to_rfc3339();
+
+  server
+    .mock("GET", "/api/v1/repos/alice/tool")
+    .with_status(200)
+    .with_body(json!({"full_name": "alice/tool", "stars_count": 5, "forks_count": 1}).to_string())
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/api/v1/repos/alice/tool/languages")
+    .with_status(200)
+    .with_body(json!({"Go": 100}).to_string())
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/api/v1/repos/alice/tool/commits")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body(
+      json!([
+        {"author": {"login": "alice"}, "commit": {"author": {"name": "Alice", "date": yesterday}}},
+        {"author": null, "commit": {"author": {"name": "Bob", "date": yesterday}}},
+        {"author": {"login": "alice"}, "commit": {"author": {"name": "Alice", "date": yesterday}}}
+      ])
+      .to_string(),
+    )
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/api/v1/repos/alice/tool/pulls")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_header("x-total-count", "4")
+    .with_body("[]")
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/api/v1/repos/alice/tool/issues")
+    .match_query(Matcher::Any)
+    .with_status(200)
+    .with_body("[]")
+    .create_async()
+    .await;
+
+  let fetcher = GiteaFetcher::new(&server.url(), None).unwrap();
+  let repo = fetcher.fetch_repo_stats("alice/tool").await.unwrap();
+
+  assert_eq!(repo.stars, 5);
+  assert_eq!(repo.languages["Go"], 100.0);
+  assert_eq!(repo.pull_requests.total_count, 4);
+  assert_eq!(
+    repo.contributors,
+    vec![
+      Contributor {
+        login: "alice".to_string(),
+        contributions: 2
+      },
+      Contributor {
+        login: "Bob".to_string(),
+        contributions: 1
+      },
+    ]
+  );
+  let total: u32 = repo
+    .commit_activity
+    .iter()
+    .flat_map(|w| &w.contribution_days)
+    .map(|d| d.contribution_count)
+    .sum();
+  assert_eq!(total, 3);
+}
+
+#[tokio::test]
+async fn test_gitea_repo_stats_use_requested_range() {
+  let mut server = mockito::Server::new_async().await;
+  let range = DateRange::year(2023).unwrap();
+
+  server
+    .mock("GET", "/api/v1/repos/alice/tool")
+    .with_status(200)
+    .with_body(json!({"full_name": "alice/tool"}).to_string())
+    .create_async()
+    .await;
+  server
+    .mock("GET", "/api/v1/repos/alice/tool/languages")
+    .with_status(200)
+    .with_body("{}")
+    .create_async()
+    .await;
+  let commits = server
+    .mock("GET", "/api/v1/repos/alice/tool/commits")
+    .match_query(Matcher::AllOf(vec![
+      Matcher::UrlEncoded("since".into(), "2023-01-01T00:00:00Z".into()),
+      Matcher::UrlEncoded("until".into(), "2023-12-31T23:59:59Z".into()),
+    ]))
+    .with_status(200)
+    .with_body(
+      json!([
+        {"author": {"login": "alice"}, "commit": {"author": {"name": "Alice", "date": "2023-06-01T10:00:00Z"}}}
+      ])
+      .to_string(),
+    )
+    .create_async()
+    .await;
+  for endpoint in ["pulls", "issues"] {
+    server
+      .mock(
+        "GET",
+        format!("/api/v1/repos/alice/tool/{}", endpoint).as_str(),
+      )
+      .match_query(Matcher::Any)
+      .with_status(200)
+      .with_body("[]")
+      .create_async()
+      .await;
+  }
+
+  let options = FetchOptions {
+    range: Some(range),
+    ..Default::default()
+  };
+  let fetcher = GiteaFetcher::new(&server.url(), None)
+    .unwrap()
+    .with_options(options);
+  let repo = fetcher.fetch_repo_stats("alice/tool").await.unwrap();
+
+  commits.assert_async().await;
+  let days: Vec<_> = repo
+    .commit_activity
+    .iter()
+    .flat_map(|w| &w.contribution_days)
+    .collect();
+  assert_eq!(days.first().unwrap().date, "2023-01-01");
+  assert_eq!(days.last().unwrap().date, "2023-12-31");
+  let june_first = days.iter().find(|d| d.date == "2023-06-01").unwrap();
+  assert_eq!(june_first.contribution_count, 1);
+}
+
+fn sample_repo() -> RepoStats {
+  serde_json::from_value(json!({
+    "full_name": "octo/hello",
+    "description": "Hello world",
+    "stars": 42,
+    "forks": 7,
+    "languages": {"Rust": 100.0},
+    "commit_activity": [{"contributionDays": [
+      {"contributionCount": 2, "date": "2024-03-03"},
+      {"contributionCount": 1, "date": "2024-03-04"}
+    ]}],
+    "contributors": [{"login": "octocat", "contributions": 12}],
+    "pull_requests": {"total_count": 1, "items": [
+      {"title": "Add greeting", "repo": "octo/hello", "url": "https://github.com/octo/hello/pull/3", "number": 3}
+    ]}
+  }))
+  .unwrap()
+}
+
+#[test]
+fn test_repo_card_lines() {
+  let formatter = DisplayFormatter::new(Config::default(), VisualOptions::default()).unwrap();
+  let text = plain(&formatter.repo_lines(&sample_repo()));
+
+  assert!(text.contains("octo/hello - 3 commits this year"));
+  assert!(text.contains("Hello world"));
+  assert!(text.contains("42"));
+  assert!(text.contains("TOP CONTRIBUTORS"));
+  assert!(text.contains("12 commits"));
+  assert!(text.contains("TOP LANGUAGES"));
+  assert!(text.contains("Add greeting"));
+}
+
+#[test]
+fn test_repo_export_document() {
+  let repo = sample_repo();
+  let output = RepoExportDocument::new(&repo)
+    .render(OutputFormat::Json)
+    .unwrap();
+  let doc: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+  assert_eq!(doc["repo"]["full_name"], "octo/hello");
+  assert_eq!(doc["repo"]["contributors"][0]["contributions"], 12);
+  assert_eq!(doc["repo"]["pull_requests"]["items"][0]["number"], 3);
+}
@@ -0,0 +1,255 @@
+use gitfetch_rs::fetcher::sourcehut::SourcehutFetcher;
+use gitfetch_rs::fetcher::{FetchOptions, Fetcher};
+use gitfetch_rs::utils::calendar::DateRange;
+use mockito::Matcher;
+use serde_json::json;
+
+async fn mock_query(server: &mut mockito::Server, marker: &str, body: serde_json::Value) {
+  server
+    .mock("POST", "/query")
+    .match_header("authorization", "Bearer test-token")
+    .match_body(Matcher::Regex(marker.to_string()))
+    .with_status(200)
+    .with_body(body.to_string())
+    .create_async()
+    .await;
+}
+
+#[tokio::test]
+async fn test_get_authenticated_user() {
+  let mut server = mockito::Server::new_async().await;
+  mock_query(
+    &mut server,
+    "me",
+    json!({"data": {"me": {"username": "alice"}}}),
+  )
+  .await;
+
+  let fetcher = SourcehutFetcher::new(&server.url(), Some("test-token")).unwrap();
+  assert_eq!(fetcher.get_authenticated_user().await.unwrap(), "alice");
+}
+
+#[tokio::test]
+async fn test_fetch_user_data() {
+  let mut server = mockito::Server::new_async().await;
+  mock_query(
+    &mut server,
+    "userByName",
+    json!({"data": {"userByName": {
+      "username": "alice",
+      "canonicalName": "~alice",
+      "bio": "Mailing lists enjoyer",
+      "url": "https://alice.example",
+      "location": null,
+      "email": "alice@example.org",
+      "created": "2020-01-01T00:00:00Z"
+    }}}),
+  )
+  .await;
+
+  let fetcher = SourcehutFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let user = fetcher.fetch_user_data("~alice").await.unwrap();
+
+  assert_eq!(user.login, "alice");
+  assert_eq!(user.name.as_deref(), Some("~alice"));
+  assert_eq!(user.bio.as_deref(), Some("Mailing lists enjoyer"));
+  assert_eq!(user.blog.as_deref(), Some("https://alice.example"));
+}
+
+#[tokio::test]
+async fn test_fetch_user_stats() {
+  let mut server = mockito::Server::new_async().await;
+
+  mock_query(
+    &mut server,
+    "repositories",
+    json!({"data": {"user": {"repositories": {
+      "results": [{"name": "app"}, {"name": "dotfiles"}],
+      "cursor": null
+    }}}}),
+  )
+  .await;
+
+  mock_query(
+    &mut server,
+    "trackers",
+    json!({"data": {"user": {"trackers": {"results": [{
+      "name": "app",
+      "tickets": {"results": [
+        {"id": 1, "subject": "Crash", "status": "REPORTED",
+         "submitter": {"canonicalName": "~bob"}, "assignees": [{"canonicalName": "~alice"}]},
+        {"id": 2, "subject": "Typo", "status": "RESOLVED",
+         "submitter": {"canonicalName": "~alice"}, "assignees": []}
+      ]}
+    }]}}}}),
+  )
+  .await;
+
+  mock_query(
+    &mut server,
+    "lists",
+    json!({"data": {"user": {"lists": {"results": [{
+      "name": "app-devel",
+      "patches": {"results": [
+        {"id": 10, "subject": "[PATCH] Fix build", "status": "PROPOSED",
+         "submitter": {"canonicalName": "~bob"}},
+        {"id": 11, "subject": "[PATCH] Docs", "status": "APPLIED",
+         "submitter": {"canonicalName": "~carol"}}
+      ]}
+    }]}}}}),
+  )
+  .await;
+
+  let fetcher = SourcehutFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let stats = fetcher.fetch_user_stats("alice", None).await.unwrap();
+
+  assert_eq!(stats.total_repos, 2);
+
+  let assigned = &stats.issues.assigned;
+  assert_eq!(assigned.total_count, 1);
+  assert_eq!(assigned.items[0].title, "Crash");
+  assert_eq!(assigned.items[0].repo, "~alice/app");
+  assert_eq!(stats.issues.created.total_count, 0);
+
+  let awaiting = &stats.pull_requests.awaiting_review;
+  assert_eq!(awaiting.total_count, 1);
+  assert_eq!(awaiting.items[0].number, 10);
+}
+
+#[tokio::test]
+async fn test_fetch_user_stats_follows_cursors() {
+  let mut server = mockito::Server::new_async().await;
+
+  mock_query(
+    &mut server,
+    "repositories",
+    json!({"data": {"user": {"repositories": {"results": [], "cursor": null}}}}),
+  )
+  .await;
+
+  let ticket = |id: i64| {
+    json!({"id": id, "subject": format!("Ticket {}", id), "status": "REPORTED",
+           "submitter": {"canonicalName": "~alice"}, "assignees": []})
+  };
+
+  mock_query(
+    &mut server,
+    r#"trackers[\s\S]*"cursor":null"#,
+    json!({"data": {"user": {"trackers": {
+      "results": [{"name": "app", "tickets": {"results": [ticket(1)], "cursor": "app-2"}}],
+      "cursor": "trackers-2"
+    }}}}),
+  )
+  .await;
+
+  mock_query(
+    &mut server,
+    r#"trackers[\s\S]*"cursor":"trackers-2""#,
+    json!({"data": {"user": {"trackers": {
+      "results": [{"name": "web", "tickets": {"results": [ticket(3)], "cursor": null}}],
+      "cursor": null
+    }}}}),
+  )
+  .await;
+
+  mock_query(
+    &mut server,
+    r#"tracker\(name[\s\S]*"cursor":"app-2""#,
+    json!({"data": {"user": {"tracker": {"tickets": {"results": [ticket(2)], "cursor": null}}}}}),
+  )
+  .await;
+
+  mock_query(
+    &mut server,
+    r#"lists[\s\S]*"cursor":null"#,
+    json!({"data": {"user": {"lists": {
+      "results": [{"name": "app-devel", "patches": {"results": [], "cursor": "patches-2"}}],
+      "cursor": null
+    }}}}),
+  )
+  .await;
+
+  mock_query(
+    &mut server,
+    r#"list\(name[\s\S]*"cursor":"patches-2""#,
+    json!({"data": {"user": {"list": {"patches": {"results": [
+      {"id": 10, "subject": "[PATCH] Fix build", "status": "PROPOSED",
+       "submitter": {"canonicalName": "~bob"}}
+    ], "cursor": null}}}}}),
+  )
+  .await;
+
+  let fetcher = SourcehutFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let stats = fetcher.fetch_user_stats("alice", None).await.unwrap();
+
+  let created = &stats.issues.created;
+  assert_eq!(created.total_count, 3);
+  let numbers: Vec<i64> = created.items.iter().map(|item| item.number).collect();
+  assert_eq!(numbers, [1, 2, 3]);
+  assert_eq!(created.items[2].repo, "~alice/web");
+
+  assert_eq!(stats.pull_requests.awaiting_review.total_count, 1);
+}
+
+#[tokio::test]
+async fn test_fetch_user_stats_caps_ticket_pages() {
+  let mut server = mockito::Server::new_async().await;
+
+  mock_query(
+    &mut server,
+    "repositories",
+    json!({"data": {"user": {"repositories": {"results": [], "cursor": null}}}}),
+  )
+  .await;
+  mock_query(
+    &mut server,
+    r#"lists\(cursor"#,
+    json!({"data": {"user": {"lists": {"results": [], "cursor": null}}}}),
+  )
+  .await;
+
+  let ticket = json!({"id": 1, "subject": "Crash", "status": "REPORTED",
+                      "submitter": {"canonicalName": "~alice"}, "assignees": []});
+  mock_query(
+    &mut server,
+    r#"trackers\(cursor"#,
+    json!({"data": {"user": {"trackers": {
+      "results": [{"name": "app", "tickets": {"results": [ticket], "cursor": "more"}}],
+      "cursor": null
+    }}}}),
+  )
+  .await;
+
+  // A tracker that always has another page is cut off after ten in total
+  let more = server
+    .mock("POST", "/query")
+    .match_body(Matcher::Regex(r#"tracker\(name"#.to_string()))
+    .with_status(200)
+    .with_body(
+      json!({"data": {"user": {"tracker": {"tickets": {"results": [ticket], "cursor": "more"}}}}})
+        .to_string(),
+    )
+    .expect(9)
+    .create_async()
+    .await;
+
+  let fetcher = SourcehutFetcher::new(&server.url(), Some("test-token")).unwrap();
+  let stats = fetcher.fetch_user_stats("alice", None).await.unwrap();
+
+  more.assert_async().await;
+  assert_eq!(stats.issues.created.total_count, 10);
+}
+
+#[tokio::test]
+async fn test_fetch_user_stats_rejects_date_range() {
+  let options = FetchOptions {
+    range: Some(DateRange::year(2023).unwrap()),
+    ..Default::default()
+  };
+  let fetcher = SourcehutFetcher::new("https://git.sr.ht", None)
+    .unwrap()
+    .with_options(options);
+
+  let err = fetcher.fetch_user_stats("~alice", None).await.unwrap_err();
+  assert!(err.to_string().contains("--year/--from/--to"));
+}
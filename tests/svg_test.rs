@@ -0,0 +1,39 @@
+use gitfetch_rs::config::Config;
+use gitfetch_rs::display::formatter::{DisplayFormatter, VisualOptions};
+
+mod common;
+
+#[test]
+fn test_render_svg_card() {
+  let formatter = DisplayFormatter::new(Config::default(), VisualOptions::default()).unwrap();
+  let user = common::sample_user();
+  let stats = common::sample_stats();
+  let svg = formatter.render_svg("octocat", &user, &stats);
+
+  assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+  assert!(svg.trim_end().ends_with("</svg>"));
+  assert_eq!(svg.matches("<rect x=").count(), 3 + 2 * 2);
+  assert!(svg.contains("<title>5 contributions on 2024-02-04</title>"));
+  assert!(svg.contains(">Jan</text>"));
+  assert!(svg.contains(">Feb</text>"));
+  assert!(svg.contains("Octo &lt;Cat&gt;"));
+  assert!(svg.contains("TOP LANGUAGES"));
+  assert!(svg.contains("ACHIEVEMENTS"));
+}
+
+#[test]
+fn test_render_svg_respects_visibility() {
+  let visual_opts = VisualOptions {
+    no_grid: true,
+    no_languages: true,
+    ..Default::default()
+  };
+  let formatter = DisplayFormatter::new(Config::default(), visual_opts).unwrap();
+  let user = common::sample_user();
+  let stats = common::sample_stats();
+  let svg = formatter.render_svg("octocat", &user, &stats);
+
+  assert!(!svg.contains("<title>"));
+  assert!(!svg.contains("TOP LANGUAGES"));
+  assert!(svg.contains("contributions this year"));
+}
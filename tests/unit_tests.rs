@@ -44,13 +44,28 @@ mod display_tests {
 #[cfg(test)]
 mod cache_tests {
   use gitfetch_rs::cache::CacheManager;
-  use serde_json::json;
+  use gitfetch_rs::models::{UserData, UserStats};
 
   fn create_test_cache() -> CacheManager {
     // Create cache with 15 minute expiry
     CacheManager::new(15).unwrap()
   }
 
+  fn user(login: &str) -> UserData {
+    UserData {
+      login: login.to_string(),
+      name: Some("Test User".to_string()),
+      ..Default::default()
+    }
+  }
+
+  fn stats(total_repos: u32) -> UserStats {
+    UserStats {
+      total_repos,
+      ..Default::default()
+    }
+  }
+
   #[test]
   fn test_cache_creation() {
     let cache = create_test_cache();
@@ -64,14 +79,8 @@ mod cache_tests {
   fn test_cache_user_data() {
     let cache = create_test_cache();
 
-    let user_data = json!({
-        "login": "test_user_123",
-        "name": "Test User"
-    });
-
-    let stats = json!({
-        "total_repos": 10
-    });
+    let user_data = user("test_user_123");
+    let stats = stats(10);
 
     let result = cache.cache_user_data("test_user_123", &user_data, &stats);
     assert!(result.is_ok(), "Should cache user data successfully");
@@ -81,14 +90,8 @@ mod cache_tests {
   fn test_get_cached_user_data() {
     let cache = create_test_cache();
 
-    let user_data = json!({
-        "login": "test_user_456",
-        "name": "Test User"
-    });
-
-    let stats = json!({
-        "total_repos": 10
-    });
+    let user_data = user("test_user_456");
+    let stats = stats(10);
 
     cache
       .cache_user_data("test_user_456", &user_data, &stats)
@@ -98,7 +101,7 @@ mod cache_tests {
     assert!(cached.is_some(), "Should retrieve cached data");
 
     if let Some(data) = cached {
-      assert_eq!(data["login"], "test_user_456");
+      assert_eq!(data.login, "test_user_456");
     }
   }
 
@@ -114,8 +117,8 @@ mod cache_tests {
   fn test_clear_cache() {
     let cache = create_test_cache();
 
-    let user_data = json!({"login": "test_user_789"});
-    let stats = json!({"total_repos": 10});
+    let user_data = user("test_user_789");
+    let stats = stats(10);
 
     cache
       .cache_user_data("test_user_789", &user_data, &stats)
@@ -132,9 +135,9 @@ mod cache_tests {
   fn test_cache_multiple_users() {
     let cache = create_test_cache();
 
-    let user1 = json!({"login": "user1"});
-    let user2 = json!({"login": "user2"});
-    let stats = json!({"total_repos": 5});
+    let user1 = user("user1");
+    let user2 = user("user2");
+    let stats = stats(5);
 
     cache.cache_user_data("user1", &user1, &stats).unwrap();
     cache.cache_user_data("user2", &user2, &stats).unwrap();
@@ -149,15 +152,17 @@ mod cache_tests {
 
 #[cfg(test)]
 mod utils_tests {
-  use gitfetch_rs::utils::git;
+  use chrono::NaiveDate;
+  use gitfetch_rs::display::graph::ContributionGraph;
+  use gitfetch_rs::utils::{calendar, git};
+  use std::collections::HashMap;
 
   #[test]
   fn test_get_repo_path() {
     // This will only work if run from within a git repo
     let result = git::get_repo_path();
 
-    if result.is_ok() {
-      let path = result.unwrap();
+    if let Ok(path) = result {
       assert!(!path.is_empty(), "Repo path should not be empty");
     }
     // If not in a git repo, it should error gracefully
@@ -166,18 +171,12 @@ mod utils_tests {
   #[test]
   fn test_analyze_local_repo() {
     // This will only work if run from within a git repo
-    let result = git::analyze_local_repo();
-
-    if result.is_ok() {
-      let data = result.unwrap();
+    let result = git::analyze_local_repo(&git::LocalOptions::default());
 
-      // Check that it has the expected structure
-      // The actual structure depends on git.rs implementation
-      assert!(data.is_object(), "Should return a JSON object");
-
-      // At minimum, should have some data
-      let obj = data.as_object().unwrap();
-      assert!(!obj.is_empty(), "Should contain some data");
+    if let Ok((user_data, stats)) = result {
+      assert!(user_data.name.is_some(), "Should report the git user");
+      assert_eq!(stats.total_repos, 1);
+      assert!(!stats.contribution_graph.is_empty());
     }
     // If not in a git repo, it should error gracefully
   }
@@ -190,4 +189,154 @@ mod utils_tests {
     // Should either succeed (in git repo) or fail gracefully
     assert!(result.is_ok() || result.is_err());
   }
+
+  #[test]
+  fn test_weeks_from_daily_counts_sunday_aligned() {
+    // 2024-01-03 is a Wednesday
+    let start = NaiveDate::from_ymd_opt(2024, 1, 3).unwrap();
+    let end = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+    let mut counts = HashMap::new();
+    counts.insert(start, 3);
+    counts.insert(end, 2);
+
+    let weeks = calendar::weeks_from_daily_counts(&counts, start, end);
+
+    assert_eq!(weeks.len(), 2);
+    assert_eq!(weeks[0].contribution_days[0].date, "2023-12-31");
+    assert_eq!(weeks[0].contribution_days[3].contribution_count, 3);
+    assert_eq!(weeks[1].contribution_days.len(), 4);
+
+    let graph = ContributionGraph::from_weeks(&weeks);
+    assert_eq!(graph.calculate_streaks(), (1, 1));
+    assert_eq!(graph.calculate_total_contributions(), 5);
+  }
+
+  #[test]
+  fn test_weeks_from_daily_counts_ignores_days_outside_range() {
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    // 2026-01-01 is a Thursday, so the first week is padded with 2025-12-28..31
+    let counts: HashMap<NaiveDate, u32> = [
+      (date(2025, 12, 29), 4),
+      (date(2025, 12, 31), 2),
+      (date(2026, 1, 1), 1),
+      (date(2026, 1, 2), 3),
+      (date(2026, 1, 4), 9),
+    ]
+    .into_iter()
+    .collect();
+
+    let weeks = calendar::weeks_from_daily_counts(&counts, date(2026, 1, 1), date(2026, 1, 3));
+
+    assert_eq!(weeks.len(), 1);
+    assert_eq!(weeks[0].contribution_days[0].date, "2025-12-28");
+    assert_eq!(weeks[0].contribution_days[1].contribution_count, 0);
+    assert_eq!(weeks[0].contribution_days[3].contribution_count, 0);
+    let graph = ContributionGraph::from_weeks(&weeks);
+    assert_eq!(graph.calculate_streaks(), (0, 2));
+    assert_eq!(graph.calculate_total_contributions(), 4);
+  }
+
+  #[test]
+  fn test_date_range_chunks() {
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    let range = calendar::DateRange::new(date(2022, 6, 1), date(2023, 12, 31)).unwrap();
+
+    let chunks = range.chunks(365);
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(
+      (chunks[0].start, chunks[0].end),
+      (date(2022, 6, 1), date(2023, 5, 31))
+    );
+    assert_eq!(
+      (chunks[1].start, chunks[1].end),
+      (date(2023, 6, 1), date(2023, 12, 31))
+    );
+  }
+
+  #[test]
+  fn test_date_range_from_args() {
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+    assert_eq!(
+      calendar::DateRange::from_args(None, None, None).unwrap(),
+      None
+    );
+
+    let year = calendar::DateRange::from_args(Some(2024), None, None)
+      .unwrap()
+      .unwrap();
+    assert_eq!(
+      (year.start, year.end),
+      (date(2024, 1, 1), date(2024, 12, 31))
+    );
+    assert_eq!(year.describe(), "in 2024");
+
+    let span =
+      calendar::DateRange::from_args(None, Some(date(2024, 3, 1)), Some(date(2024, 6, 30)))
+        .unwrap()
+        .unwrap();
+    assert_eq!(span.describe(), "from 2024-03-01 to 2024-06-30");
+    assert!(span.contains(date(2024, 6, 30)));
+    assert!(!span.contains(date(2024, 7, 1)));
+
+    let until = calendar::DateRange::from_args(None, None, Some(date(2023, 12, 31)))
+      .unwrap()
+      .unwrap();
+    assert_eq!(until.start, date(2023, 1, 1));
+
+    assert!(
+      calendar::DateRange::from_args(None, Some(date(2024, 2, 1)), Some(date(2024, 1, 1))).is_err()
+    );
+  }
+}
+
+#[cfg(test)]
+mod models_tests {
+  use gitfetch_rs::models::{UserData, UserStats};
+  use serde_json::json;
+
+  #[test]
+  fn test_user_stats_from_legacy_cache_row() {
+    // Rows cached before the typed model stored bare counters and nulls
+    let stats: UserStats = serde_json::from_value(json!({
+      "total_stars": 4,
+      "languages": null,
+      "contribution_graph": [{"contributionDays": [{"contributionCount": 2, "date": "2024-01-07"}]}],
+      "pull_requests": {"open": 3, "awaiting_review": 0, "mentions": 1},
+      "issues": {"assigned": {"total_count": 2, "items": [{"title": "Bug", "repo": "a/b", "url": "", "number": 9}]}}
+    }))
+    .unwrap();
+
+    assert_eq!(stats.total_stars, 4);
+    assert!(stats.languages.is_empty());
+    assert_eq!(
+      stats.contribution_graph[0].contribution_days[0].contribution_count,
+      2
+    );
+    assert_eq!(stats.pull_requests.open.total_count, 3);
+    assert!(stats.pull_requests.open.items.is_empty());
+    assert_eq!(stats.issues.assigned.items[0].number, 9);
+    assert_eq!(stats.issues.created.total_count, 0);
+  }
+
+  #[test]
+  fn test_user_data_from_github_payload() {
+    let user: UserData = serde_json::from_value(json!({
+      "login": "octocat",
+      "id": 583231,
+      "name": "The Octocat",
+      "bio": null,
+      "blog": "https://github.blog",
+      "public_repos": 8,
+      "followers": 100,
+      "site_admin": false
+    }))
+    .unwrap();
+
+    assert_eq!(user.login, "octocat");
+    assert_eq!(user.id, Some(583231));
+    assert_eq!(user.bio, None);
+    assert_eq!(user.blog.as_deref(), Some("https://github.blog"));
+    assert_eq!(user.public_repos, 8);
+  }
 }